@@ -0,0 +1,462 @@
+/// Anonymized copies of real puzzle inputs, which can be shared and committed as test fixtures
+/// where the inputs themselves must not be published.
+///
+/// Every day has a spec of transforms that keep the input structurally equivalent: it still follows
+/// the format and the constraints of real inputs, and has the same size and shape, but its values
+/// and line order differ. Answers usually change, so `cargo anonymize <day>` solves the
+/// transformed input and records its answers next to it.
+///
+/// Days without a spec have no transform that changes their values while keeping their structure.
+/// Rotating, mirroring or relabeling alone is trivially undone, so the word search of day 4 and the
+/// maps of days 6 and 8 cannot be shared. Moving antennas of day 8 changes which antinodes overlap.
+use std::collections::{HashMap, HashSet};
+
+use crate::equation::{evaluate, Equation, Op, ADD_MUL, ADD_MUL_CONCAT};
+use crate::random::Rng;
+use crate::template::Day;
+
+/// A change of an input that keeps it structurally equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Shuffles the lines of every section, for inputs where their order does not matter.
+    /// Sections are separated by blank lines.
+    ShuffleLines,
+    /// Replaces every number with another one of the same number of digits, consistently across
+    /// the whole input, so equal numbers stay equal and different numbers stay different.
+    /// Zero and numbers with leading zeros are kept.
+    RenumberValues,
+    /// Draws new levels within `min..=max` for every report of space-separated levels. Steps keep their
+    /// sign and whether they are between 1 and 3, so reports that are safe, safe after removing one level,
+    /// or unsafe stay so.
+    RedrawReports { min: i64, max: i64 },
+    /// Replaces the numbers of every `target: numbers` equation with new numbers of the same number
+    /// of digits, and picks a new target. Equations that are solvable with `+` and `*`, only with `||`,
+    /// or not at all stay so.
+    RenumberEquations,
+}
+
+/// The transforms that anonymize the input of a day, applied in order.
+#[derive(Debug)]
+pub struct Spec {
+    pub day: u8,
+    pub transforms: &'static [Transform],
+}
+
+impl Spec {
+    pub fn apply(&self, input: &str, rng: &mut Rng) -> String {
+        self.transforms
+            .iter()
+            .fold(input.to_string(), |input, transform| {
+                transform.apply(&input, rng)
+            })
+    }
+}
+
+pub const SPECS: &[Spec] = &[
+    // location ids only matter for sorting and counting, and the lists are unordered.
+    Spec {
+        day: 1,
+        transforms: &[Transform::RenumberValues, Transform::ShuffleLines],
+    },
+    // safety depends on the steps between levels, which are redrawn within their class.
+    Spec {
+        day: 2,
+        transforms: &[
+            Transform::RedrawReports { min: 1, max: 99 },
+            Transform::ShuffleLines,
+        ],
+    },
+    // `do()` and `don't()` apply across lines, so the order stays.
+    Spec {
+        day: 3,
+        transforms: &[Transform::RenumberValues],
+    },
+    // page numbers are labels, and neither rules nor updates are ordered.
+    Spec {
+        day: 5,
+        transforms: &[Transform::RenumberValues, Transform::ShuffleLines],
+    },
+    // the numbers of an equation depend on each other, so they are replaced together.
+    Spec {
+        day: 7,
+        transforms: &[Transform::RenumberEquations, Transform::ShuffleLines],
+    },
+];
+
+/// Looks up the transform spec of a day.
+pub fn find(day: Day) -> Option<&'static Spec> {
+    SPECS.iter().find(|s| s.day == day.into_inner())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Transform {
+    pub fn apply(&self, input: &str, rng: &mut Rng) -> String {
+        let output = match *self {
+            Transform::ShuffleLines => shuffle_lines(input, rng),
+            Transform::RenumberValues => renumber_values(input, rng),
+            Transform::RedrawReports { min, max } => {
+                map_lines(input, |line| redraw_report(line, min, max, rng))
+            }
+            Transform::RenumberEquations => map_lines(input, |line| renumber_equation(line, rng)),
+        };
+        // transforms work on lines, so restore a trailing line break.
+        match (input.ends_with('\n'), output.ends_with('\n')) {
+            (true, false) => output + "\n",
+            _ => output,
+        }
+    }
+}
+
+fn map_lines(input: &str, mut f: impl FnMut(&str) -> String) -> String {
+    input.lines().map(&mut f).collect::<Vec<_>>().join("\n")
+}
+
+fn shuffle_lines(input: &str, rng: &mut Rng) -> String {
+    input
+        .trim_end_matches('\n')
+        .split("\n\n")
+        .map(|section| {
+            let mut lines: Vec<&str> = section.lines().collect();
+            rng.shuffle(&mut lines);
+            lines.join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Replaces every run of digits in `text` with the result of `f`.
+fn map_numbers(text: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        out.push_str(&f(&rest[..end]));
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn renumber_values(input: &str, rng: &mut Rng) -> String {
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut used: HashSet<String> = HashSet::new();
+    map_numbers(input, |digits| {
+        // longer numbers do not fit into a `u64`.
+        if digits.starts_with('0') || digits.len() > 18 {
+            return digits.to_string();
+        }
+        if let Some(number) = mapping.get(digits) {
+            return number.clone();
+        }
+        let low = 10u64.pow(digits.len() as u32 - 1);
+        // there are as many numbers with this digit count as candidates, so one is always free.
+        let number = loop {
+            let candidate = rng.range(low..low * 10).to_string();
+            if used.insert(candidate.clone()) {
+                break candidate;
+            }
+        };
+        mapping.insert(digits.to_string(), number.clone());
+        number
+    })
+}
+
+/// Upper bound of random reports tried before the original one is kept.
+const REPORT_ATTEMPTS: usize = 1000;
+
+/// Whether the steps of a report all increase or all decrease by 1 to 3, and whether they do
+/// after removing one level.
+fn report_safety(levels: &[i64]) -> (bool, bool) {
+    let safe = |levels: &[i64]| {
+        let steps = || levels.windows(2).map(|w| w[1] - w[0]);
+        steps().all(|d| (1..=3).contains(&d)) || steps().all(|d| (-3..=-1).contains(&d))
+    };
+    let dampened = (0..levels.len()).any(|skip| {
+        let rest: Vec<i64> = [&levels[..skip], &levels[skip + 1..]].concat();
+        safe(&rest)
+    });
+    (safe(levels), dampened)
+}
+
+fn redraw_report(line: &str, min: i64, max: i64, rng: &mut Rng) -> String {
+    let Ok(levels) = line
+        .split(' ')
+        .map(str::parse)
+        .collect::<Result<Vec<i64>, _>>()
+    else {
+        return line.to_string();
+    };
+    let class = report_safety(&levels);
+
+    for _ in 0..REPORT_ATTEMPTS {
+        let steps: Vec<i64> = levels
+            .windows(2)
+            .map(|w| {
+                let step = w[1] - w[0];
+                let size = match step.abs() {
+                    0 => 0,
+                    1..=3 => rng.range(1..4) as i64,
+                    large => rng.range(4..large as u64 + 1) as i64,
+                };
+                size * step.signum()
+            })
+            .collect();
+
+        // offsets of the levels from the first one, which is placed so that all of them are in range.
+        let offsets: Vec<i64> = std::iter::once(0)
+            .chain(steps.iter().scan(0, |level, step| {
+                *level += step;
+                Some(*level)
+            }))
+            .collect();
+        let (low, high) = (
+            min - offsets.iter().min().unwrap(),
+            max - offsets.iter().max().unwrap(),
+        );
+        if low > high {
+            continue;
+        }
+        let first = low + rng.below((high - low + 1) as u64) as i64;
+        let redrawn: Vec<i64> = offsets.iter().map(|o| first + o).collect();
+
+        if report_safety(&redrawn) == class {
+            return redrawn
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
+    line.to_string()
+}
+
+/// Upper bound of random equations tried before the original one is kept.
+const EQUATION_ATTEMPTS: usize = 1000;
+
+fn renumber_equation(line: &str, rng: &mut Rng) -> String {
+    let Ok(equation) = line.parse::<Equation>() else {
+        return line.to_string();
+    };
+    let solution = equation
+        .solve(ADD_MUL)
+        .or_else(|| equation.solve(ADD_MUL_CONCAT));
+
+    for _ in 0..EQUATION_ATTEMPTS {
+        let numbers: Vec<u64> = equation
+            .numbers
+            .iter()
+            .map(|n| {
+                let low = 10u64.pow(n.to_string().len() as u32 - 1);
+                rng.range(low..low * 10)
+            })
+            .collect();
+        let target = match &solution {
+            Some(ops) => evaluate(&numbers, ops),
+            // an unsolvable equation is close to a solvable one.
+            None => {
+                let ops: Vec<Op> = (1..numbers.len())
+                    .map(|_| *rng.pick(ADD_MUL_CONCAT))
+                    .collect();
+                evaluate(&numbers, &ops).and_then(|t| t.checked_add(rng.range(1..10)))
+            }
+        };
+        let Some(target) = target else {
+            continue;
+        };
+        let renumbered = Equation { target, numbers };
+        let class = |e: &Equation| (e.is_solvable(ADD_MUL), e.is_solvable(ADD_MUL_CONCAT));
+        if class(&renumbered) == class(&equation) {
+            return renumbered.to_string();
+        }
+    }
+    line.to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{find, report_safety, Transform, SPECS};
+    #[allow(unused_imports)]
+    use crate::{
+        equation::{Equation, ADD_MUL, ADD_MUL_CONCAT},
+        generate,
+        ordering::OrderingRules,
+        random::Rng,
+        template::Day,
+    };
+    #[allow(unused_imports)]
+    use std::collections::HashSet;
+
+    /// Properties of an input that the transforms of its day have to keep, so that the anonymized
+    /// input is still a valid input of the same shape.
+    #[allow(dead_code)]
+    fn structure(day: u8, input: &str) -> Vec<usize> {
+        let numbers = |line: &str| -> Vec<i64> {
+            line.split(|c: char| !c.is_ascii_digit())
+                .filter_map(|n| n.parse().ok())
+                .collect()
+        };
+        let lines: Vec<Vec<i64>> = input.lines().map(numbers).collect();
+        match day {
+            // pairs of ids, and how often the left ids appear in the right column.
+            1 => {
+                let similarity = lines
+                    .iter()
+                    .map(|l| lines.iter().filter(|r| r[1] == l[0]).count())
+                    .sum();
+                vec![lines.iter().filter(|l| l.len() == 2).count(), similarity]
+            }
+            // safe reports, with and without removing a level, and levels between 1 and 99.
+            2 => {
+                let safety: Vec<(bool, bool)> = lines.iter().map(|r| report_safety(r)).collect();
+                vec![
+                    safety.iter().filter(|s| s.0).count(),
+                    safety.iter().filter(|s| s.1).count(),
+                    lines
+                        .iter()
+                        .flatten()
+                        .filter(|l| (1..=99).contains(*l))
+                        .count(),
+                ]
+            }
+            // valid instructions, and the length of every line.
+            3 => {
+                let mul = regex::Regex::new(r"mul\(\d{1,3},\d{1,3}\)").unwrap();
+                let mut structure = vec![mul.find_iter(input).count(), input.matches("do").count()];
+                structure.extend(input.lines().map(str::len));
+                structure
+            }
+            // rules, and updates that are already ordered.
+            5 => {
+                let (rules, updates) = input.split_once("\n\n").unwrap();
+                let rules: OrderingRules<u32> = rules.parse().unwrap();
+                let updates: Vec<Vec<u32>> = updates
+                    .lines()
+                    .map(|l| l.split(',').map(|p| p.parse().unwrap()).collect())
+                    .collect();
+                let ordered = updates.iter().filter(|u| rules.is_ordered(u)).count();
+                vec![rules.len(), updates.len(), ordered]
+            }
+            // equations solvable with `+` and `*`, and with `||`.
+            7 => {
+                let equations: Vec<Equation> = input.lines().map(|l| l.parse().unwrap()).collect();
+                vec![
+                    equations.iter().filter(|e| e.is_solvable(ADD_MUL)).count(),
+                    equations
+                        .iter()
+                        .filter(|e| e.is_solvable(ADD_MUL_CONCAT))
+                        .count(),
+                ]
+            }
+            _ => unreachable!("day {day} has no spec"),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn apply(transform: Transform, input: &str) -> String {
+        transform.apply(input, &mut Rng::new(0x2024))
+    }
+
+    #[test]
+    fn renumbers_consistently() {
+        let input = "12 345 12\n7 0 345 0042\n";
+        let output = apply(Transform::RenumberValues, input);
+        let fields: Vec<&str> = output.split_whitespace().collect();
+        assert_eq!(fields.len(), 7);
+        assert_eq!(fields[0], fields[2]);
+        assert_eq!(fields[1], fields[5]);
+        assert_ne!(fields[0], "12");
+        assert_eq!(
+            (fields[0].len(), fields[1].len(), fields[3].len()),
+            (2, 3, 1)
+        );
+        assert_eq!((fields[4], fields[6]), ("0", "0042"));
+        assert!(output.ends_with('\n'));
+
+        // a full set of one-digit numbers is permuted.
+        let digits = apply(Transform::RenumberValues, "1 2 3 4 5 6 7 8 9");
+        let mut sorted: Vec<&str> = digits.split(' ').collect();
+        sorted.sort_unstable();
+        assert_eq!(sorted.join(" "), "1 2 3 4 5 6 7 8 9");
+    }
+
+    #[test]
+    fn redraws_reports_within_their_class() {
+        let input = "7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5\n8 6 4 4 1\n90 95 99 99 99\n";
+        let output = apply(Transform::RedrawReports { min: 1, max: 99 }, input);
+        assert_ne!(output, input);
+        let levels = |input: &str| -> Vec<Vec<i64>> {
+            input
+                .lines()
+                .map(|l| l.split(' ').map(|n| n.parse().unwrap()).collect())
+                .collect()
+        };
+        for (before, after) in levels(input).iter().zip(levels(&output)) {
+            assert_eq!(report_safety(before), report_safety(&after), "{after:?}");
+            assert_eq!(after.len(), before.len());
+            assert!(after.iter().all(|l| (1..=99).contains(l)));
+        }
+    }
+
+    #[test]
+    fn shuffles_lines_within_sections() {
+        let input = "a\nb\nc\nd\n\n1\n2\n3\n";
+        let output = apply(Transform::ShuffleLines, input);
+        let (first, second) = output.split_once("\n\n").unwrap();
+        let mut first: Vec<&str> = first.lines().collect();
+        first.sort_unstable();
+        assert_eq!(first, vec!["a", "b", "c", "d"]);
+        assert_eq!(second.lines().count(), 3);
+        assert!(output.ends_with("\n") && !output.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn keeps_generated_inputs_valid() {
+        for spec in SPECS {
+            let day = Day::new(spec.day).unwrap();
+            for seed in 0..5 {
+                let input = generate::find(day)
+                    .unwrap()
+                    .generate(&mut Rng::new(seed), 30);
+                let output = spec.apply(&input, &mut Rng::new(seed));
+                assert_ne!(output, input, "day {day}");
+                assert_eq!(output.lines().count(), input.lines().count(), "day {day}");
+                assert_eq!(
+                    structure(spec.day, &output),
+                    structure(spec.day, &input),
+                    "day {day}"
+                );
+                assert!(output.ends_with('\n'), "day {day}");
+            }
+        }
+        for day in [4, 6, 8, 25] {
+            assert!(find(Day::new(day).unwrap()).is_none());
+        }
+    }
+
+    #[test]
+    fn renumbers_equations() {
+        let input = "190: 10 19\n156: 15 6\n83: 17 5\n";
+        let output = apply(Transform::RenumberEquations, input);
+        assert_ne!(output, input);
+        let equations: Vec<Equation> = output.lines().map(|l| l.parse().unwrap()).collect();
+        assert!(equations[0].is_solvable(ADD_MUL));
+        assert!(!equations[1].is_solvable(ADD_MUL) && equations[1].is_solvable(ADD_MUL_CONCAT));
+        assert!(!equations[2].is_solvable(ADD_MUL_CONCAT));
+        assert_eq!(
+            equations[0]
+                .numbers
+                .iter()
+                .map(|n| n.to_string().len())
+                .collect::<Vec<_>>(),
+            [2, 2]
+        );
+    }
+}
@@ -1,13 +1,20 @@
 use std::collections::HashMap;
 
+use advent_of_code::parse::pair;
+
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut first, mut second): (Vec<_>, Vec<_>) = input
+fn parse(input: &str) -> Option<(Vec<u32>, Vec<u32>)> {
+    let pairs = input
         .lines()
-        .map(|line| line.split_once("   ").unwrap())
-        .map(|(a, b)| (a.parse::<u32>().unwrap(), b.parse::<u32>().unwrap()))
-        .unzip();
+        .map(|line| pair::<u32, u32>(line, " "))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    Some(pairs.into_iter().unzip())
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let (mut first, mut second) = parse(input)?;
 
     first.sort();
     second.sort();
@@ -22,16 +29,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (first, second): (Vec<_>, Vec<_>) = input
-        .lines()
-        .map(|line| line.split_once(' ').unwrap())
-        .map(|(a, b)| {
-            (
-                a.trim().parse::<u32>().unwrap(),
-                b.trim().parse::<u32>().unwrap(),
-            )
-        })
-        .unzip();
+    let (first, second) = parse(input)?;
 
     let mut map: HashMap<u32, u32> = HashMap::new();
     for &value in second.iter() {
@@ -52,6 +50,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::check::{shrink_lines, shrink_numbers, Check};
 
     #[test]
     fn test_part_one() {
@@ -64,4 +63,29 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(31));
     }
+
+    /// Counts the occurrences in the right list for every number of the left list.
+    fn part_two_reference(input: &str) -> Option<u32> {
+        let (first, second) = parse(input)?;
+        Some(
+            first
+                .iter()
+                .map(|a| a * second.iter().filter(|&b| b == a).count() as u32)
+                .sum(),
+        )
+    }
+
+    #[test]
+    fn test_part_two_matches_reference() {
+        // small values, so that numbers repeat in both lists.
+        Check::new(|rng| {
+            let lines: Vec<String> = (0..rng.range(1..50))
+                .map(|_| format!("{}   {}", rng.range(1..20), rng.range(1..20)))
+                .collect();
+            lines.join("\n")
+        })
+        .seed(0x2024_0001)
+        .shrink(|input| [shrink_lines(input), shrink_numbers(input)].concat())
+        .differential(part_two, part_two_reference);
+    }
 }
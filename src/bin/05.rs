@@ -1,87 +1,47 @@
-#![allow(clippy::manual_inspect)]
-use std::{cmp::Ordering, collections::HashMap};
+use advent_of_code::ordering::OrderingRules;
+use advent_of_code::parse::{list, sections};
 
 advent_of_code::solution!(5);
 
-enum OrderingRule {
-    Before(i32),
-    After(i32),
-}
-
-type RulesHashMap = HashMap<i32, Vec<OrderingRule>>;
-
-fn ordering(a: &i32, b: &i32, rules: &RulesHashMap) -> Ordering {
-    rules.get(a).map_or(Ordering::Equal, |rules| {
-        rules
-            .iter()
-            .find_map(|rule| match rule {
-                OrderingRule::Before(n) if b == n => Some(Ordering::Less),
-                OrderingRule::After(n) if b == n => Some(Ordering::Greater),
-                _ => None,
-            })
-            .unwrap_or(Ordering::Equal)
-    })
-}
-
-fn rules_check(rules: &RulesHashMap, update: Vec<i32>) -> i32 {
-    if update.is_sorted_by(|a, b| ordering(a, b, rules).is_le()) {
-        return update[update.len() / 2];
-    }
-    0
+fn middle(update: &[u32]) -> u32 {
+    update[update.len() / 2]
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    parse(input).map(|(dependancies, updates)| {
+    parse(input).map(|(rules, updates)| {
         updates
             .iter()
-            .map(|update| rules_check(&dependancies, update.clone()))
-            .sum::<i32>() as u32
+            .filter(|update| rules.is_ordered(update))
+            .map(|update| middle(update))
+            .sum()
     })
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    parse(input).map(|(dependancies, mut updates)| {
+    parse(input).map(|(rules, mut updates)| {
         updates
             .iter_mut()
-            .filter(|update| !update.is_sorted_by(|a, b| ordering(a, b, &dependancies).is_le()))
+            .filter(|update| !rules.is_ordered(update))
             .map(|update| {
-                update.sort_by(|a, b| ordering(a, b, &dependancies));
-                update
+                rules.sort(update);
+                middle(update)
             })
-            .map(|update| rules_check(&dependancies, update.clone()))
-            .sum::<i32>() as u32
+            .sum()
     })
 }
 
-fn parse(input: &str) -> Option<(RulesHashMap, Vec<Vec<i32>>)> {
-    input.split_once("\n\n").map(|(deps, pages)| {
-        (
-            deps.lines()
-                .fold(HashMap::<i32, Vec<OrderingRule>>::new(), |mut map, line| {
-                    line.split_once('|')
-                        .map(|(before, after)| {
-                            let before = before.parse().unwrap();
-                            let after = after.parse().unwrap();
-                            map.entry(before)
-                                .or_default()
-                                .push(OrderingRule::Before(after));
-                            map.entry(after)
-                                .or_default()
-                                .push(OrderingRule::After(before));
-                        })
-                        .unwrap();
-                    map
-                }),
-            pages
-                .lines()
-                .map(|line| {
-                    line.split(',')
-                        .map(|page| page.parse::<i32>().unwrap())
-                        .collect::<Vec<i32>>()
-                })
-                .collect::<Vec<Vec<i32>>>(),
-        )
-    })
+fn parse(input: &str) -> Option<(OrderingRules<u32>, Vec<Vec<u32>>)> {
+    let mut sections = sections(input);
+    let (rules, pages) = (sections.next()?, sections.next()?);
+
+    let rules = rules.parse().ok()?;
+    let updates = pages
+        .lines()
+        .map(|line| list(line, ","))
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    Some((rules, updates))
 }
 
 #[cfg(test)]
@@ -1,130 +1,239 @@
-use itertools::Itertools;
-use pathfinding::matrix::{directions, Matrix};
+use advent_of_code::bitgrid::BitGrid;
+use advent_of_code::direction::Direction;
+use advent_of_code::grid::{Coord, Grid};
+use advent_of_code::render::{self, Palette, Picture, Recording, Rgb};
+use advent_of_code::trace;
+use advent_of_code::visualize::{self, Color, Frame};
+use advent_of_code::walker::Walker;
 
 advent_of_code::solution!(6);
 
-type Direction = (isize, isize);
-type PositionAndDirection = ((usize, usize), Direction);
+type PositionAndDirection = (Coord, Direction);
 
-fn get_direction(value: &char) -> Direction {
-    match value {
-        '^' => directions::N,
-        'v' => directions::S,
-        '<' => directions::W,
-        '>' => directions::E,
-        c => panic!("value not handled: {}", c),
-    }
+fn get_start(grid: &Grid<char>) -> Option<PositionAndDirection> {
+    grid.iter()
+        .find_map(|(coord, c)| Some((coord, Direction::try_from(*c).ok()?)))
 }
 
-fn get_starting_position(matrix: &Matrix<char>) -> Option<(usize, usize)> {
-    matrix.iter().enumerate().find_map(|(x, row)| {
-        row.iter()
-            .enumerate()
-            .find_map(|(y, c)| if *c == '^' { Some((x, y)) } else { None })
-    })
+fn parse(input: &str) -> Option<(Grid<char>, PositionAndDirection)> {
+    let _span = trace::span("parse");
+    let grid: Grid<char> = input.parse().ok()?;
+    let start = get_start(&grid)?;
+    advent_of_code::trace!(
+        "{}x{} grid, guard at {} facing {:?}",
+        grid.width(),
+        grid.height(),
+        start.0,
+        start.1
+    );
+    Some((grid, start))
 }
 
-fn build_path(
-    matrix: &Matrix<char>,
-    start_position: (usize, usize),
-    mut direction: (isize, isize),
-    already_visited: &[PositionAndDirection],
-) -> Result<Vec<PositionAndDirection>, ()> {
-    let mut position = start_position;
-    let mut visited = Vec::from(already_visited);
-
-    while let Some(new_position) = matrix.move_in_direction(position, direction) {
-        let value = matrix.get(new_position);
-        match value {
-            Some('#') => direction = rotate_90(direction),
-            Some('.') => {
-                position = new_position;
-                if visited.contains(&(position, direction)) {
-                    return Err(());
-                }
-                visited.push((position, direction));
-            }
-            _ => panic!("value should not be there"),
-        }
+pub fn part_one(input: &str) -> Option<usize> {
+    let (grid, (start_position, direction)) = parse(input)?;
+
+    let mut walker = Walker::new(&grid, |c| *c == '#');
+    walker.walk(start_position, direction);
+
+    if visualize::enabled() {
+        visualize::show(
+            Frame::new(&grid)
+                .highlight(walker.visited(), Color::Cyan)
+                .highlight([start_position], Color::Yellow)
+                .caption(format!("{} cells visited", walker.visited_cells())),
+        );
+    }
+
+    if render::enabled() {
+        render_walk(&grid, &walker, start_position, direction);
     }
-    Ok(visited)
+
+    Some(walker.visited_cells())
 }
 
-fn rotate_90(direction: (isize, isize)) -> (isize, isize) {
-    match direction {
-        directions::N => directions::E,
-        directions::E => directions::S,
-        directions::S => directions::W,
-        directions::W => directions::N,
-        _ => panic!("direction not handled"),
+/// Saves the guard's path as an image and the walk as an animation with one frame per turn.
+fn render_walk<F: Fn(&char) -> bool>(
+    grid: &Grid<char>,
+    walker: &Walker<char, F>,
+    start: Coord,
+    heading: Direction,
+) {
+    let palette = Palette::new(Rgb::BLACK).with('#', Rgb::GRAY);
+    let background = Picture::new(grid, &palette);
+    render::save(
+        DAY,
+        "path.png",
+        &background
+            .clone()
+            .paint(walker.visited(), Rgb::CYAN)
+            .paint([start], Rgb::YELLOW),
+    );
+
+    let mut recording = Recording::default();
+    let mut visited = BitGrid::for_grid(grid);
+    let mut state = (start, heading);
+    while let Some(next) = walker.step(state.0, state.1) {
+        visited.insert(state.0);
+        if next.1 != state.1 {
+            recording.push(
+                background
+                    .clone()
+                    .paint(visited.iter(), Rgb::CYAN)
+                    .paint([state.0], Rgb::YELLOW),
+            );
+        }
+        state = next;
     }
+    visited.insert(state.0);
+    recording.push(background.paint(visited.iter(), Rgb::CYAN));
+    render::save(DAY, "walk.gif", &recording);
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut matrix = Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap();
-    let start_position = get_starting_position(&matrix)?;
-    let direction = get_direction(matrix.get(start_position).unwrap());
+/// For every cell and heading, the cell in which the guard stops before the next obstacle,
+/// or `None` if it walks off the grid. Lets the guard move obstacle to obstacle.
+struct JumpTable {
+    width: usize,
+    stops: Vec<Option<Coord>>,
+}
 
-    *matrix.get_mut(start_position).unwrap() = '.';
+impl JumpTable {
+    fn new(grid: &Grid<char>) -> Self {
+        let mut stops = vec![None; grid.len() * 4];
+        let coords: Vec<Coord> = grid.coords().collect();
 
-    let visited = build_path(&matrix, start_position, direction, &[]).unwrap();
-    let length = visited
-        .iter()
-        .map(|(position, _)| position)
-        .unique()
-        .collect::<Vec<&(usize, usize)>>()
-        .len();
+        for heading in Direction::ORTHOGONAL {
+            // visit cells so that the cell ahead is always resolved first.
+            let ordered: Box<dyn Iterator<Item = &Coord>> = match heading {
+                Direction::N | Direction::W => Box::new(coords.iter()),
+                _ => Box::new(coords.iter().rev()),
+            };
 
-    Some(length)
-}
+            for &coord in ordered {
+                let stop = match grid.offset(coord, heading) {
+                    None => None,
+                    Some(ahead) if grid[ahead] == '#' => Some(coord),
+                    Some(ahead) => stops[key(grid.width(), ahead, heading)],
+                };
+                stops[key(grid.width(), coord, heading)] = stop;
+            }
+        }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let mut matrix = Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap();
-    let start_position = get_starting_position(&matrix)?;
-    let direction = get_direction(matrix.get(start_position).unwrap());
-
-    *matrix.get_mut(start_position).unwrap() = '.';
-
-    let visited = build_path(&matrix, start_position, direction, &[]).unwrap();
-
-    let length = visited
-        .iter()
-        .filter(|position_and_direction| {
-            is_obstruction_result_in_infinite_loop(
-                position_and_direction,
-                &matrix,
-                &visited
-                    .iter()
-                    .take_while(|x| x == position_and_direction)
-                    .copied()
-                    .collect::<Vec<PositionAndDirection>>(),
-            )
-        })
-        .count();
+        Self {
+            width: grid.width(),
+            stops,
+        }
+    }
+
+    /// Where the guard stops when walking from `from` towards `heading`, with an additional `obstacle`.
+    fn stop(&self, from: Coord, heading: Direction, obstacle: Coord) -> Option<Coord> {
+        let stop = self.stops[key(self.width, from, heading)];
+
+        // the obstacle blocks the way if it lies between `from` (exclusive) and the stop (inclusive).
+        let blocks = match heading {
+            Direction::N => {
+                obstacle.x == from.x
+                    && obstacle.y < from.y
+                    && stop.is_none_or(|s| obstacle.y >= s.y)
+            }
+            Direction::S => {
+                obstacle.x == from.x
+                    && obstacle.y > from.y
+                    && stop.is_none_or(|s| obstacle.y <= s.y)
+            }
+            Direction::W => {
+                obstacle.y == from.y
+                    && obstacle.x < from.x
+                    && stop.is_none_or(|s| obstacle.x >= s.x)
+            }
+            Direction::E => {
+                obstacle.y == from.y
+                    && obstacle.x > from.x
+                    && stop.is_none_or(|s| obstacle.x <= s.x)
+            }
+            _ => unreachable!("the guard only walks orthogonally"),
+        };
 
-    Some(length)
+        if blocks {
+            obstacle.offset(heading.turn_around())
+        } else {
+            stop
+        }
+    }
+}
+
+fn key(width: usize, coord: Coord, heading: Direction) -> usize {
+    (coord.y * width + coord.x) * 4 + heading.index() / 2
 }
 
-fn is_obstruction_result_in_infinite_loop(
-    (position, direction): &PositionAndDirection,
-    map: &Matrix<char>,
-    already_visited: &[PositionAndDirection],
+/// Whether the guard loops when starting at `position` with `heading` and `obstacle` placed on the grid.
+/// States are stamped with `stamp` in `seen` instead of clearing it for every obstacle.
+fn is_loop(
+    jumps: &JumpTable,
+    mut position: Coord,
+    mut heading: Direction,
+    obstacle: Coord,
+    seen: &mut [u32],
+    stamp: u32,
 ) -> bool {
-    let mut new_matrix = map.clone();
-    let obstruction_position = new_matrix.move_in_direction(*position, *direction);
-    if obstruction_position.is_none() {
-        return false;
+    while let Some(stop) = jumps.stop(position, heading, obstacle) {
+        position = stop;
+        heading = heading.turn_right();
+
+        let state = key(jumps.width, position, heading);
+        if seen[state] == stamp {
+            return true;
+        }
+        seen[state] = stamp;
     }
-    *new_matrix.get_mut(obstruction_position.unwrap()).unwrap() = '#';
-    match build_path(&new_matrix, *position, *direction, already_visited) {
-        Err(()) => true,
-        Ok(_) => false,
+    false
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let (grid, (start_position, start_direction)) = parse(input)?;
+
+    // the walk below assumes that the guard leaves the grid, which is what the puzzle promises.
+    if Walker::new(&grid, |c| *c == '#')
+        .walk(start_position, start_direction)
+        .is_loop()
+    {
+        return None;
     }
+
+    let jumps = JumpTable::new(&grid);
+    let mut seen = vec![0; grid.len() * 4];
+    let mut tried = BitGrid::for_grid(&grid);
+    tried.insert(start_position);
+
+    let (mut position, mut direction) = (start_position, start_direction);
+    let mut loops = 0;
+
+    // walk the original path. An obstruction is only tested where the path first reaches a cell,
+    // as the guard would have hit it earlier otherwise. The walk resumes from the state in front of it.
+    while let Some(ahead) = grid.offset(position, direction) {
+        if grid[ahead] == '#' {
+            direction = direction.turn_right();
+            continue;
+        }
+
+        if tried.insert(ahead) {
+            let stamp = (grid.index_of(ahead)? + 1) as u32;
+            if is_loop(&jumps, position, direction, ahead, &mut seen, stamp) {
+                advent_of_code::trace!(Trace, "obstruction at {ahead} traps the guard");
+                loops += 1;
+            }
+        }
+        position = ahead;
+    }
+
+    Some(loops)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::check::{shrink_grid, Check};
+    use advent_of_code::generate;
+    use advent_of_code::random::Rng;
 
     #[test]
     fn test_part_one() {
@@ -133,9 +242,9 @@ mod tests {
     }
 
     #[test]
-    fn matrix() {
-        let m = Matrix::new_square(8, '.');
-        assert_eq!(m.move_in_direction((1, 1), (2, 1)), Some((3, 2)));
+    fn start() {
+        let grid: Grid<char> = "..\n.<".parse().unwrap();
+        assert_eq!(get_start(&grid), Some((Coord::new(1, 1), Direction::W)));
     }
 
     #[test]
@@ -151,4 +260,79 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_part_two_boxed_in_guard() {
+        assert_eq!(part_two(".#.\n#^#\n.#."), None);
+        assert_eq!(part_two(".#..\n...#\n#^..\n..#."), None);
+    }
+
+    /// Tries an obstruction on every cell of the original path and simulates each walk step by step.
+    fn part_two_brute_force(input: &str) -> Option<usize> {
+        let (grid, (start_position, direction)) = parse(input)?;
+
+        let mut walker = Walker::new(&grid, |c| *c == '#');
+        walker.walk(start_position, direction);
+        let candidates: Vec<Coord> = walker.visited().filter(|c| *c != start_position).collect();
+
+        let loops = candidates
+            .into_iter()
+            .filter(|obstruction| {
+                walker.set_obstacle(Some(*obstruction));
+                walker.walk(start_position, direction).is_loop()
+            })
+            .count();
+
+        Some(loops)
+    }
+
+    /// Like real inputs, the guard has to leave the grid without obstructions.
+    fn leaves_grid(input: &str) -> bool {
+        parse(input).is_some_and(|(grid, (position, direction))| {
+            !Walker::new(&grid, |c| *c == '#')
+                .walk(position, direction)
+                .is_loop()
+        })
+    }
+
+    /// A map with obstructions of random density and a guard facing a random direction.
+    fn guard_map(rng: &mut Rng) -> String {
+        let width = rng.range(4..24) as usize;
+        let height = rng.range(4..24) as usize;
+        let density = rng.range(5..20);
+
+        let mut cells: Vec<char> = (0..width * height)
+            .map(|_| if rng.chance(density) { '#' } else { '.' })
+            .collect();
+        let start = rng.index(cells.len());
+        cells[start] = *rng.pick(&['^', '>', 'v', '<']);
+
+        cells
+            .chunks(width)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_part_two_matches_brute_force() {
+        Check::new(guard_map)
+            .cases(500)
+            .seed(0x2024_0006)
+            .assume(leaves_grid)
+            .shrink(shrink_grid('.'))
+            .differential(part_two, part_two_brute_force);
+    }
+
+    #[test]
+    fn test_part_two_matches_brute_force_on_generated_maps() {
+        Check::new(|rng| {
+            let size = rng.range(5..30) as usize;
+            generate::guard_map(rng, size)
+        })
+        .cases(100)
+        .assume(leaves_grid)
+        .shrink(shrink_grid('.'))
+        .differential(part_two, part_two_brute_force);
+    }
 }
@@ -1,64 +1,17 @@
-advent_of_code::solution!(7);
-
-fn parse(input: &str) -> Vec<(i64, Vec<i64>)> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_once(':')
-                .map(|(result, nums)| {
-                    (
-                        result.parse::<i64>().unwrap(),
-                        nums.trim()
-                            .split(' ')
-                            .map(|num| num.parse().unwrap())
-                            .collect::<Vec<i64>>(),
-                    )
-                })
-                .unwrap()
-        })
-        .collect()
-}
-
-pub fn part_one(input: &str) -> Option<i64> {
-    Some(
-        parse(input)
-            .iter()
-            .filter(|(r, n)| check(*r, n, 0))
-            .map(|(r, _)| r)
-            .sum::<i64>(),
-    )
-}
+use advent_of_code::equation::{calibration_total, Equation, ADD_MUL, ADD_MUL_CONCAT};
 
-fn check(val: i64, math: &[i64], acc: i64) -> bool {
-    if math.is_empty() {
-        return val == acc;
-    }
-    check(val, &math[1..], acc + math[0]) || check(val, &math[1..], acc * math[0])
-}
+advent_of_code::solution!(7);
 
-fn check2(val: i64, math: &[i64], acc: i64) -> bool {
-    if math.is_empty() {
-        return val == acc;
-    }
-    let conc = concatenate_numbers(acc, math[0]);
-    check2(val, &math[1..], acc + math[0])
-        || check2(val, &math[1..], acc * math[0])
-        || check2(val, &math[1..], conc)
+fn parse(input: &str) -> Option<Vec<Equation>> {
+    input.lines().map(|line| line.parse().ok()).collect()
 }
 
-fn concatenate_numbers(a: i64, b: i64) -> i64 {
-    let concatenated = format!("{}{}", a, b);
-    concatenated.parse::<i64>().unwrap()
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(calibration_total(&parse(input)?, ADD_MUL))
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    Some(
-        parse(input)
-            .iter()
-            .filter(|(r, n)| check2(*r, n, 0))
-            .map(|(r, _)| *r)
-            .sum::<i64>(),
-    )
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(calibration_total(&parse(input)?, ADD_MUL_CONCAT))
 }
 
 #[cfg(test)]
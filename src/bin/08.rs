@@ -1,95 +1,95 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use geo::Coord;
+use advent_of_code::bitgrid::BitGrid;
+use advent_of_code::grid::Grid;
+use advent_of_code::point::Point2;
+use advent_of_code::render::{self, Picture, Rgb};
+use advent_of_code::visualize::{self, Color, Frame};
 use itertools::Itertools;
-use pathfinding::matrix::Matrix;
 
 advent_of_code::solution!(8);
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let matrix = Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap();
+    let grid: Grid<char> = input.parse().ok()?;
 
-    let antenas = get_antenas(&matrix);
-
-    fn antinodes(
-        antenas: &HashMap<char, Vec<Coord<isize>>>,
-        matrix: Matrix<char>,
-    ) -> HashSet<Coord<isize>> {
+    let antenas = get_antenas(&grid);
+    let mut antinodes = BitGrid::for_grid(&grid);
+    antinodes.extend(
         antenas
-            .iter()
-            .flat_map(|(_, ants)| {
-                ants.iter().tuple_combinations().flat_map(|(a, b)| {
-                    [
-                        Coord {
-                            x: 2 * a.x - b.x,
-                            y: 2 * a.y - b.y,
-                        },
-                        Coord {
-                            x: 2 * b.x - a.x,
-                            y: 2 * b.y - a.y,
-                        },
-                    ]
-                })
+            .values()
+            .flat_map(|ants| {
+                ants.iter()
+                    .tuple_combinations()
+                    .flat_map(|(&a, &b)| [a + (a - b), b + (b - a)])
             })
-            .filter(|point| matrix.get((point.x as usize, point.y as usize)).is_some())
-            .collect()
-    }
+            .filter_map(|p| p.to_coord().filter(|c| grid.in_bounds(*c))),
+    );
 
-    Some(antinodes(&antenas, matrix).len())
+    Some(antinodes.len())
 }
 
-fn get_antenas(matrix: &Matrix<char>) -> HashMap<char, Vec<Coord<isize>>> {
-    let mut antenas: HashMap<char, Vec<Coord<isize>>> = HashMap::new();
-    for (x, row) in matrix.iter().enumerate() {
-        for (y, value) in row.iter().enumerate() {
-            if *value != '.' {
-                antenas
-                    .entry(*value)
-                    .or_default()
-                    .push((x as isize, y as isize).into());
-            }
+fn get_antenas(grid: &Grid<char>) -> HashMap<char, Vec<Point2>> {
+    let mut antenas: HashMap<char, Vec<Point2>> = HashMap::new();
+    for (coord, value) in grid.iter() {
+        if *value != '.' {
+            antenas
+                .entry(*value)
+                .or_default()
+                .push(Point2::try_from(coord).unwrap());
         }
     }
     antenas
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let matrix = Matrix::from_rows(input.lines().map(|l| l.chars())).unwrap();
+    let grid: Grid<char> = input.parse().ok()?;
 
-    let antenas = get_antenas(&matrix);
+    let antenas = get_antenas(&grid);
+    let in_bounds = |p: &Point2| p.to_coord().is_some_and(|c| grid.in_bounds(c));
 
-    fn antinodes(
-        antenas: &HashMap<char, Vec<Coord<isize>>>,
-        matrix: Matrix<char>,
-    ) -> HashSet<Coord<isize>> {
+    let mut antinodes = BitGrid::for_grid(&grid);
+    antinodes.extend(
         antenas
-            .iter()
-            .flat_map(|(_, ants)| {
-                ants.iter().tuple_combinations().flat_map(|(a, b)| {
-                    (0..)
-                        .map(|i| Coord {
-                            x: b.x + i * (a.x - b.x),
-                            y: b.y + i * (a.y - b.y),
-                        })
-                        .take_while(|point| {
-                            matrix.get((point.x as usize, point.y as usize)).is_some()
-                        })
-                        .chain(
-                            (2..)
-                                .map(|i| Coord {
-                                    x: a.x + i * (b.x - a.x),
-                                    y: a.y + i * (b.y - a.y),
-                                })
-                                .take_while(|point| {
-                                    matrix.get((point.x as usize, point.y as usize)).is_some()
-                                }),
-                        )
+            .values()
+            .flat_map(|ants| {
+                ants.iter().tuple_combinations().flat_map(|(&a, &b)| {
+                    let step = (a - b).reduced();
+                    let forward = (0..).map(move |i| a + step * i).take_while(in_bounds);
+                    let backward = (1..).map(move |i| a - step * i).take_while(in_bounds);
+                    forward.chain(backward)
                 })
             })
-            .collect()
+            .filter_map(Point2::to_coord),
+    );
+
+    if visualize::enabled() {
+        visualize::show(
+            Frame::new(&grid)
+                .mark(antinodes.iter(), '#', Color::Magenta)
+                .highlight(grid.find_all(|c| *c != '.'), Color::Yellow)
+                .caption(format!("{} antinodes", antinodes.len())),
+        );
+    }
+
+    if render::enabled() {
+        // antennas on top of antinodes, with one color per frequency.
+        render::save(
+            DAY,
+            "antinodes.svg",
+            &Picture::with_colors(&grid, |c| frequency_color(*c))
+                .paint(antinodes.iter().filter(|c| grid[*c] == '.'), Rgb::MAGENTA),
+        );
     }
 
-    Some(antinodes(&antenas, matrix).len())
+    Some(antinodes.len())
+}
+
+fn frequency_color(c: char) -> Rgb {
+    const COLORS: [Rgb; 5] = [Rgb::YELLOW, Rgb::CYAN, Rgb::GREEN, Rgb::BLUE, Rgb::RED];
+    match c {
+        '.' => Rgb::BLACK,
+        _ => COLORS[c as usize % COLORS.len()],
+    }
 }
 
 #[cfg(test)]
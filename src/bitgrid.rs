@@ -0,0 +1,410 @@
+/// Bit-packed sets for dense grid searches: visited cells ([`BitGrid`]) and visited
+/// cell and heading pairs ([`StateSet`]). Every operation is a few word operations, no hashing.
+use std::fmt::Display;
+
+use crate::direction::Direction;
+use crate::grid::{Coord, Grid};
+
+/// A fixed-size set of the integers `0..capacity`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns whether the value was newly inserted.
+    ///
+    /// # Panics
+    /// If `i` is not below the capacity.
+    pub fn insert(&mut self, i: usize) -> bool {
+        assert!(i < self.capacity, "{i} is outside of the set");
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let is_new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        is_new
+    }
+
+    /// Returns whether the value was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        if i >= self.capacity {
+            return false;
+        }
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let was_present = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        was_present
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.capacity && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The values of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// Adds all values of `other`.
+    ///
+    /// # Panics
+    /// If the capacities differ.
+    pub fn union_with(&mut self, other: &Self) {
+        assert_eq!(self.capacity, other.capacity, "sets differ in capacity");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Keeps only the values that are also in `other`.
+    ///
+    /// # Panics
+    /// If the capacities differ.
+    pub fn intersect_with(&mut self, other: &Self) {
+        assert_eq!(self.capacity, other.capacity, "sets differ in capacity");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of cells of a `width` × `height` grid.
+///
+/// ```
+/// # use advent_of_code::{bitgrid::BitGrid, grid::Coord};
+/// let mut seen = BitGrid::new(3, 2);
+/// assert!(seen.insert(Coord::new(2, 1)));
+/// assert!(!seen.insert(Coord::new(2, 1)));
+/// assert!(!seen.contains(Coord::new(5, 5)));
+/// assert_eq!(seen.to_string(), "...\n..#");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    bits: BitSet,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits: BitSet::new(width * height),
+        }
+    }
+
+    /// An empty set for the cells of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, coord: Coord) -> Option<usize> {
+        (coord.x < self.width && coord.y < self.height).then(|| coord.y * self.width + coord.x)
+    }
+
+    /// Returns whether the cell was newly inserted.
+    ///
+    /// # Panics
+    /// If `coord` is outside of the grid.
+    pub fn insert(&mut self, coord: Coord) -> bool {
+        let index = self.index(coord).expect("cell is outside of the grid");
+        self.bits.insert(index)
+    }
+
+    pub fn remove(&mut self, coord: Coord) -> bool {
+        self.index(coord).is_some_and(|i| self.bits.remove(i))
+    }
+
+    /// Whether the cell is in the set. Cells outside of the grid never are.
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.index(coord).is_some_and(|i| self.bits.contains(i))
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// The cells of the set in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.bits
+            .iter()
+            .map(|i| Coord::new(i % self.width, i / self.width))
+    }
+
+    /// # Panics
+    /// If the grid sizes differ.
+    pub fn union_with(&mut self, other: &Self) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        self.bits.union_with(&other.bits);
+    }
+
+    /// # Panics
+    /// If the grid sizes differ.
+    pub fn intersect_with(&mut self, other: &Self) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        self.bits.intersect_with(&other.bits);
+    }
+}
+
+/// Inserts every cell. Panics on cells outside of the grid, like [`BitGrid::insert`].
+impl Extend<Coord> for BitGrid {
+    fn extend<I: IntoIterator<Item = Coord>>(&mut self, iter: I) {
+        for coord in iter {
+            self.insert(coord);
+        }
+    }
+}
+
+/// Renders cells in the set as `#` and all others as `.`.
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                let c = if self.contains(Coord::new(x, y)) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of `(cell, heading)` states of a `width` × `height` grid, e.g. to detect when a walker loops.
+/// Every cell has room for all eight headings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateSet {
+    width: usize,
+    height: usize,
+    bits: BitSet,
+}
+
+const HEADINGS: usize = Direction::ALL.len();
+
+impl StateSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits: BitSet::new(width * height * HEADINGS),
+        }
+    }
+
+    /// An empty set for the states on `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    fn index(&self, coord: Coord, heading: Direction) -> Option<usize> {
+        (coord.x < self.width && coord.y < self.height)
+            .then(|| (coord.y * self.width + coord.x) * HEADINGS + heading.index())
+    }
+
+    /// Returns whether the state was newly inserted.
+    ///
+    /// # Panics
+    /// If `coord` is outside of the grid.
+    pub fn insert(&mut self, coord: Coord, heading: Direction) -> bool {
+        let index = self
+            .index(coord, heading)
+            .expect("cell is outside of the grid");
+        self.bits.insert(index)
+    }
+
+    pub fn remove(&mut self, coord: Coord, heading: Direction) -> bool {
+        self.index(coord, heading)
+            .is_some_and(|i| self.bits.remove(i))
+    }
+
+    pub fn contains(&self, coord: Coord, heading: Direction) -> bool {
+        self.index(coord, heading)
+            .is_some_and(|i| self.bits.contains(i))
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// The states of the set, by cell in row-major order and then by heading.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, Direction)> + '_ {
+        self.bits.iter().map(|i| {
+            let cell = i / HEADINGS;
+            (
+                Coord::new(cell % self.width, cell / self.width),
+                Direction::ALL[i % HEADINGS],
+            )
+        })
+    }
+
+    /// The cells with at least one heading in the set.
+    pub fn cells(&self) -> BitGrid {
+        let mut cells = BitGrid::new(self.width, self.height);
+        for (coord, _) in self.iter() {
+            cells.insert(coord);
+        }
+        cells
+    }
+
+    /// # Panics
+    /// If the grid sizes differ.
+    pub fn union_with(&mut self, other: &Self) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        self.bits.union_with(&other.bits);
+    }
+
+    /// # Panics
+    /// If the grid sizes differ.
+    pub fn intersect_with(&mut self, other: &Self) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        self.bits.intersect_with(&other.bits);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{BitGrid, BitSet, StateSet};
+    #[allow(unused_imports)]
+    use crate::random::Rng;
+    #[allow(unused_imports)]
+    use crate::{direction::Direction, grid::Coord};
+    #[allow(unused_imports)]
+    use std::collections::HashSet;
+
+    #[test]
+    fn stores_bits_across_words() {
+        let mut set = BitSet::new(130);
+        for i in [0, 63, 64, 129] {
+            assert!(set.insert(i));
+        }
+        assert!(!set.insert(64));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 63, 64, 129]);
+        assert_eq!(set.len(), 4);
+        assert!(set.remove(63));
+        assert!(!set.remove(63));
+        assert!(!set.contains(500));
+
+        let mut other = BitSet::new(130);
+        other.insert(0);
+        other.insert(100);
+        let mut union = set.clone();
+        union.union_with(&other);
+        assert_eq!(union.iter().collect::<Vec<_>>(), vec![0, 64, 100, 129]);
+        set.intersect_with(&other);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn tracks_states() {
+        let mut states = StateSet::new(3, 3);
+        assert!(states.insert(Coord::new(1, 2), Direction::E));
+        assert!(states.insert(Coord::new(1, 2), Direction::NW));
+        assert!(states.insert(Coord::new(0, 0), Direction::S));
+        assert!(!states.insert(Coord::new(1, 2), Direction::E));
+        assert!(!states.contains(Coord::new(1, 2), Direction::W));
+        assert!(!states.contains(Coord::new(3, 0), Direction::E));
+        assert_eq!(
+            states.iter().collect::<Vec<_>>(),
+            vec![
+                (Coord::new(0, 0), Direction::S),
+                (Coord::new(1, 2), Direction::E),
+                (Coord::new(1, 2), Direction::NW)
+            ]
+        );
+        assert_eq!(states.cells().len(), 2);
+    }
+
+    /// Compares a bit grid with a hash set under random inserts and removals.
+    #[test]
+    fn matches_hash_set() {
+        let mut rng = Rng::new(0x2024);
+
+        let (width, height) = (13, 11);
+        let mut grid = BitGrid::new(width, height);
+        let mut model = HashSet::new();
+        for _ in 0..2000 {
+            let coord = Coord::new(rng.index(width), rng.index(height));
+            if rng.below(3) == 0 {
+                assert_eq!(grid.remove(coord), model.remove(&coord));
+            } else {
+                assert_eq!(grid.insert(coord), model.insert(coord));
+            }
+            assert_eq!(grid.len(), model.len());
+        }
+
+        let mut expected: Vec<_> = model.into_iter().collect();
+        expected.sort_by_key(|c| (c.y, c.x));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,391 @@
+/// Randomized tests of solutions on generated inputs: differential tests against a slow, obviously correct
+/// reference implementation, and property tests. A failing input is shrunk to a small counterexample before
+/// it is reported, so the panic message shows an input that can be debugged by hand. Panics of the tested code
+/// count as failures and are shrunk the same way.
+///
+/// ```
+/// # use advent_of_code::check::{shrink_numbers, Check};
+/// fn sum_fast(input: &str) -> u64 {
+///     input.lines().filter_map(|l| l.parse::<u64>().ok()).sum()
+/// }
+/// fn sum_reference(input: &str) -> u64 {
+///     let mut sum = 0;
+///     for line in input.lines() {
+///         sum += line.parse::<u64>().unwrap_or(0);
+///     }
+///     sum
+/// }
+/// Check::new(|rng| {
+///     let lines: Vec<String> = (0..rng.range(1..20)).map(|_| rng.below(1000).to_string()).collect();
+///     lines.join("\n")
+/// })
+/// .shrink(|input| shrink_numbers(input))
+/// .differential(sum_fast, sum_reference);
+/// ```
+use std::fmt::{Debug, Display};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::random::Rng;
+
+/// Upper bound of inputs tried while shrinking a counterexample.
+const SHRINK_BUDGET: usize = 10_000;
+
+type Shrinker<'a> = Box<dyn Fn(&str) -> Vec<String> + 'a>;
+
+pub struct Check<'a> {
+    generate: Box<dyn Fn(&mut Rng) -> String + 'a>,
+    assume: Box<dyn Fn(&str) -> bool + 'a>,
+    shrink: Shrinker<'a>,
+    cases: usize,
+    seed: u64,
+}
+
+/// A failing input, after shrinking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    /// Index of the generated case that failed first.
+    pub case: usize,
+    pub seed: u64,
+    pub input: String,
+    /// How often a smaller failing input was found.
+    pub shrink_steps: usize,
+    pub message: String,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "counterexample in case {} of seed {:#x}, shrunk in {} steps:",
+            self.case, self.seed, self.shrink_steps
+        )?;
+        writeln!(f, "{}", self.input)?;
+        write!(f, "{}", self.message)
+    }
+}
+
+impl<'a> Check<'a> {
+    /// Tests the inputs returned by `generate`. Inputs have to follow the puzzle's format.
+    pub fn new(generate: impl Fn(&mut Rng) -> String + 'a) -> Self {
+        Self {
+            generate: Box::new(generate),
+            assume: Box::new(|_| true),
+            shrink: Box::new(shrink_lines),
+            cases: 200,
+            seed: 0x2024,
+        }
+    }
+
+    /// Number of generated inputs. Defaults to 200.
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skips inputs that violate a constraint of real inputs, both when generating and when shrinking.
+    pub fn assume(mut self, valid: impl Fn(&str) -> bool + 'a) -> Self {
+        self.assume = Box::new(valid);
+        self
+    }
+
+    /// Sets how to find smaller variants of a failing input, most promising first.
+    /// Defaults to [`shrink_lines`].
+    pub fn shrink(mut self, shrink: impl Fn(&str) -> Vec<String> + 'a) -> Self {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    /// Checks that `solution` and `reference` agree on every input.
+    ///
+    /// # Panics
+    /// With the shrunk counterexample if they disagree, or if every input was skipped.
+    #[track_caller]
+    pub fn differential<T: PartialEq + Debug>(
+        &self,
+        solution: impl Fn(&str) -> T,
+        reference: impl Fn(&str) -> T,
+    ) {
+        self.assert(|input| {
+            let (actual, expected) = (solution(input), reference(input));
+            (actual != expected)
+                .then(|| format!("solution returned {actual:?}, reference returned {expected:?}"))
+        });
+    }
+
+    /// Checks that `holds` is true for every input.
+    ///
+    /// # Panics
+    /// With the shrunk counterexample if it is false, or if every input was skipped.
+    #[track_caller]
+    pub fn property(&self, holds: impl Fn(&str) -> bool) {
+        self.assert(|input| (!holds(input)).then(|| "property does not hold".to_string()));
+    }
+
+    #[track_caller]
+    fn assert(&self, fails: impl Fn(&str) -> Option<String>) {
+        match self.run(fails) {
+            Ok(0) => panic!("all {} generated inputs were skipped", self.cases),
+            Ok(_) => {}
+            Err(counterexample) => panic!("{counterexample}"),
+        }
+    }
+
+    /// Tests every generated input with `fails`, which describes why an input fails.
+    /// Returns the number of tested inputs, or the first failure after shrinking it.
+    pub fn run(&self, fails: impl Fn(&str) -> Option<String>) -> Result<usize, Counterexample> {
+        let mut tested = 0;
+        for case in 0..self.cases {
+            let input = (self.generate)(&mut Rng::new(self.seed.wrapping_add(case as u64)));
+            if !(self.assume)(&input) {
+                continue;
+            }
+            tested += 1;
+            if let Some(message) = failure(&fails, &input) {
+                let (input, message, shrink_steps) = self.minimize(input, message, &fails);
+                return Err(Counterexample {
+                    case,
+                    seed: self.seed,
+                    input,
+                    shrink_steps,
+                    message,
+                });
+            }
+        }
+        Ok(tested)
+    }
+
+    /// Greedily replaces the input with its first smaller variant that still fails, until none does.
+    fn minimize(
+        &self,
+        mut input: String,
+        mut message: String,
+        fails: &impl Fn(&str) -> Option<String>,
+    ) -> (String, String, usize) {
+        let mut steps = 0;
+        let mut budget = SHRINK_BUDGET;
+        'shrink: while budget > 0 {
+            for candidate in (self.shrink)(&input) {
+                if budget == 0 {
+                    break 'shrink;
+                }
+                budget -= 1;
+                if !(self.assume)(&candidate) {
+                    continue;
+                }
+                if let Some(m) = failure(fails, &candidate) {
+                    (input, message) = (candidate, m);
+                    steps += 1;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        (input, message, steps)
+    }
+}
+
+/// Calls `fails`, and treats a panic as a failure whose message is the panic payload.
+fn failure(fails: &impl Fn(&str) -> Option<String>, input: &str) -> Option<String> {
+    panic::catch_unwind(AssertUnwindSafe(|| fails(input))).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".to_string());
+        Some(format!("panicked: {message}"))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Variants of `input` with a block of lines removed, largest blocks first.
+pub fn shrink_lines(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut candidates = Vec::new();
+    let mut size = lines.len() / 2;
+    while size > 0 {
+        for start in (0..lines.len()).step_by(size) {
+            let end = (start + size).min(lines.len());
+            let kept: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+            candidates.push(kept.join("\n"));
+        }
+        size /= 2;
+    }
+    if lines.len() == 1 {
+        // a single line can only become empty.
+        candidates.push(String::new());
+    }
+    candidates
+}
+
+/// Variants of `input` with one number made smaller: zero, half of it, or one less.
+pub fn shrink_numbers(input: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let bytes = input.as_bytes();
+    let mut start = 0;
+    while start < bytes.len() {
+        if !bytes[start].is_ascii_digit() {
+            start += 1;
+            continue;
+        }
+        let end = start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+        if let Ok(value) = input[start..end].parse::<u64>() {
+            let mut smaller = vec![0, value / 2, value.saturating_sub(1)];
+            smaller.dedup();
+            for n in smaller.into_iter().filter(|&n| n < value) {
+                candidates.push(format!("{}{n}{}", &input[..start], &input[end..]));
+            }
+        }
+        start = end;
+    }
+    candidates
+}
+
+/// Shrinks character grids: removes a row or column, or replaces a cell with `blank`.
+pub fn shrink_grid(blank: char) -> impl Fn(&str) -> Vec<String> {
+    move |input| {
+        let rows: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        let width = rows.first().map_or(0, Vec::len);
+        let render = |rows: &[Vec<char>]| {
+            rows.iter()
+                .map(|row| row.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let mut candidates = Vec::new();
+        if rows.len() > 1 {
+            for y in 0..rows.len() {
+                let mut smaller = rows.clone();
+                smaller.remove(y);
+                candidates.push(render(&smaller));
+            }
+        }
+        if width > 1 {
+            for x in 0..width {
+                let smaller: Vec<Vec<char>> = rows
+                    .iter()
+                    .map(|row| {
+                        let mut row = row.clone();
+                        if x < row.len() {
+                            row.remove(x);
+                        }
+                        row
+                    })
+                    .collect();
+                candidates.push(render(&smaller));
+            }
+        }
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                if cell != blank {
+                    let mut simpler = rows.clone();
+                    simpler[y][x] = blank;
+                    candidates.push(render(&simpler));
+                }
+            }
+        }
+        candidates
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{shrink_grid, shrink_lines, shrink_numbers, Check};
+
+    #[test]
+    fn shrinks_to_minimal_counterexamples() {
+        // claims no line is larger than 900.
+        let check = Check::new(|rng| {
+            let lines: Vec<String> = (0..rng.range(5..30))
+                .map(|_| rng.below(1000).to_string())
+                .collect();
+            lines.join("\n")
+        })
+        .shrink(|input| [shrink_lines(input), shrink_numbers(input)].concat());
+
+        let counterexample = check
+            .run(|input| {
+                input
+                    .lines()
+                    .any(|l| l.parse::<u32>().unwrap() > 900)
+                    .then(|| "too large".to_string())
+            })
+            .unwrap_err();
+        assert_eq!(counterexample.input, "901");
+        assert_eq!(counterexample.message, "too large");
+        assert!(counterexample.shrink_steps > 0);
+        assert!(counterexample
+            .to_string()
+            .starts_with("counterexample in case 0 of seed 0x2024"));
+    }
+
+    #[test]
+    fn skips_invalid_inputs() {
+        let check = Check::new(|rng| rng.below(10).to_string()).cases(50);
+        let even = check.assume(|input| input.parse::<u32>().unwrap() % 2 == 0);
+        let tested = even.run(|input| input.ends_with('5').then(String::new));
+        assert!(matches!(tested, Ok(n) if n > 0 && n < 50));
+
+        even.differential(|input| input.len(), |input| input.chars().count());
+        even.property(|input| input != "3");
+    }
+
+    #[test]
+    #[should_panic(expected = "solution returned 2, reference returned 1")]
+    fn reports_disagreements() {
+        Check::new(|rng| "#".repeat(rng.range(1..5) as usize))
+            .shrink(shrink_grid('.'))
+            .differential(|input| input.len().min(2), |input| input.len().min(1));
+    }
+
+    #[test]
+    fn reports_panics_as_failures() {
+        let counterexample = Check::new(|rng| {
+            let lines: Vec<String> = (0..rng.range(5..30))
+                .map(|_| rng.below(1000).to_string())
+                .collect();
+            lines.join("\n")
+        })
+        .run(|input| {
+            let large = input.lines().filter(|l| l.len() == 3).count();
+            assert!(large < 3, "{large} numbers with three digits");
+            None
+        })
+        .unwrap_err();
+        assert_eq!(counterexample.input.lines().count(), 3);
+        assert_eq!(
+            counterexample.message,
+            "panicked: 3 numbers with three digits"
+        );
+    }
+
+    #[test]
+    fn shrinks_grids() {
+        let candidates = shrink_grid('.')("#.\n.#");
+        assert_eq!(
+            candidates,
+            vec![".#", "#.", ".\n#", "#\n.", "..\n.#", "#.\n.."]
+        );
+        assert_eq!(
+            shrink_lines("a\nb\nc\nd"),
+            vec!["c\nd", "a\nb", "b\nc\nd", "a\nc\nd", "a\nb\nd", "a\nb\nc"]
+        );
+        assert_eq!(
+            shrink_numbers("x 10 y 1"),
+            vec!["x 0 y 1", "x 5 y 1", "x 9 y 1", "x 10 y 0"]
+        );
+    }
+}
@@ -0,0 +1,240 @@
+/// Compass headings for grid walkers. `y` grows downwards, so north is `(0, -1)`.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// One of the eight compass directions.
+/// Four-way walkers use [`Direction::ORTHOGONAL`], eight-way walkers [`Direction::ALL`].
+///
+/// ```
+/// # use advent_of_code::direction::Direction;
+/// let heading: Direction = '^'.try_into().unwrap();
+/// assert_eq!(heading.turn_right(), Direction::E);
+/// assert_eq!(heading.delta(), (0, -1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction; 8] = [N, NE, E, SE, S, SW, W, NW];
+
+    /// The four orthogonal directions, clockwise starting north.
+    pub const ORTHOGONAL: [Direction; 4] = [N, E, S, W];
+
+    /// The four diagonal directions, clockwise starting north-east.
+    pub const DIAGONAL: [Direction; 4] = [NE, SE, SW, NW];
+
+    /// Position in [`Direction::ALL`], i.e. the number of 45° clockwise turns from north.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    const fn from_index(index: usize) -> Self {
+        Self::ALL[index % 8]
+    }
+
+    /// Turns 90° counter-clockwise.
+    pub const fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 6)
+    }
+
+    /// Turns 90° clockwise.
+    pub const fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Turns 180°.
+    pub const fn turn_around(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// Turns 45° counter-clockwise.
+    pub const fn turn_left_45(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    /// Turns 45° clockwise.
+    pub const fn turn_right_45(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The `(dx, dy)` step of one move in this direction.
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            N => (0, -1),
+            NE => (1, -1),
+            E => (1, 0),
+            SE => (1, 1),
+            S => (0, 1),
+            SW => (-1, 1),
+            W => (-1, 0),
+            NW => (-1, -1),
+        }
+    }
+
+    /// The direction of a unit `(dx, dy)` step, if there is one.
+    pub fn from_delta(delta: (isize, isize)) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.delta() == delta)
+    }
+
+    /// Arrow of an orthogonal direction as used in puzzle inputs, e.g. `^`.
+    pub const fn arrow(self) -> Option<char> {
+        match self {
+            N => Some('^'),
+            E => Some('>'),
+            S => Some('v'),
+            W => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for (isize, isize) {
+    fn from(direction: Direction) -> Self {
+        direction.delta()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            N => "N",
+            NE => "NE",
+            E => "E",
+            SE => "SE",
+            S => "S",
+            SW => "SW",
+            W => "W",
+            NW => "NW",
+        };
+        f.write_str(name)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses arrows (`^v<>`), compass letters (`NESW`) and up/down/left/right (`UDLR`).
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromStrError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(N),
+            '>' | 'E' | 'R' => Ok(E),
+            'v' | 'S' | 'D' => Ok(S),
+            '<' | 'W' | 'L' => Ok(W),
+            _ => Err(DirectionFromStrError),
+        }
+    }
+}
+
+/// Parses everything [`Direction::try_from`] accepts for a `char`, as well as diagonals like `NE`.
+impl FromStr for Direction {
+    type Err = DirectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, None) => Self::try_from(c),
+            (Some('N'), Some('E'), None) => Ok(NE),
+            (Some('S'), Some('E'), None) => Ok(SE),
+            (Some('S'), Some('W'), None) => Ok(SW),
+            (Some('N'), Some('W'), None) => Ok(NW),
+            _ => Err(DirectionFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Direction`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirectionFromStrError;
+
+impl Error for DirectionFromStrError {}
+
+impl Display for DirectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `^v<>`, `NESW`, `UDLR` or a diagonal like `NE`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::Direction::{self, *};
+
+    #[test]
+    fn turns() {
+        assert_eq!(N.turn_right(), E);
+        assert_eq!(N.turn_left(), W);
+        assert_eq!(NE.turn_right(), SE);
+        assert_eq!(W.turn_around(), E);
+        assert_eq!(N.turn_right_45(), NE);
+        assert_eq!(N.turn_left_45(), NW);
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.turn_around());
+        }
+    }
+
+    #[test]
+    fn parses() {
+        assert_eq!(
+            "^v<>"
+                .chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec![N, S, W, E])
+        );
+        assert_eq!(
+            "URDL"
+                .chars()
+                .map(Direction::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec![N, E, S, W])
+        );
+        assert_eq!("NW".parse(), Ok(NW));
+        assert!("X".parse::<Direction>().is_err());
+        assert!("NN".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn converts_deltas() {
+        for d in Direction::ALL {
+            assert_eq!(Direction::from_delta(d.delta()), Some(d));
+            assert_eq!(d.turn_around().delta(), (-d.delta().0, -d.delta().1));
+        }
+        assert_eq!(Direction::from_delta((2, 0)), None);
+        assert_eq!(
+            Direction::ORTHOGONAL
+                .iter()
+                .filter(|d| d.is_diagonal())
+                .count(),
+            0
+        );
+        assert_eq!(
+            Direction::DIAGONAL
+                .iter()
+                .filter(|d| d.is_diagonal())
+                .count(),
+            4
+        );
+    }
+}
@@ -0,0 +1,317 @@
+/// Solver for puzzles that insert operators between numbers to reach a target, e.g. `190: 10 19`.
+/// Operators are evaluated left to right. The solver works backwards from the target,
+/// so impossible branches are pruned before they are expanded.
+use std::fmt::Display;
+use std::str::FromStr;
+use std::thread;
+
+use crate::math::Integer;
+use crate::parse::{key_values, ParseError};
+
+/// A binary operator that can be undone, which is what allows searching backwards.
+pub trait Operator: Copy {
+    /// Applies the operator to the accumulated value and the next number.
+    fn apply(self, acc: u64, value: u64) -> Option<u64>;
+
+    /// Returns the accumulated value `acc` for which `apply(acc, value) == target`, if there is one.
+    fn invert(self, target: u64, value: u64) -> Option<u64>;
+
+    /// Returns true if `apply(acc, value) == target` for every accumulated value, e.g. `acc * 0 == 0`.
+    /// The search cannot undo such an operator, so it only checks that the remaining numbers evaluate.
+    fn absorbs(self, _target: u64, _value: u64) -> bool {
+        false
+    }
+
+    fn symbol(self) -> &'static str;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Mul,
+    /// Joins the digits of both numbers, e.g. `12 || 345 = 12345`.
+    Concat,
+}
+
+pub const ADD_MUL: &[Op] = &[Op::Add, Op::Mul];
+pub const ADD_MUL_CONCAT: &[Op] = &[Op::Add, Op::Mul, Op::Concat];
+
+impl Operator for Op {
+    fn apply(self, acc: u64, value: u64) -> Option<u64> {
+        match self {
+            Op::Add => acc.checked_add(value),
+            Op::Mul => acc.checked_mul(value),
+            Op::Concat => acc.checked_concat(value),
+        }
+    }
+
+    fn invert(self, target: u64, value: u64) -> Option<u64> {
+        match self {
+            Op::Add => target.checked_sub(value),
+            // with a zero factor, any accumulated value works. That case is handled by `absorbs`.
+            Op::Mul => (value != 0 && target.is_multiple_of(value)).then(|| target / value),
+            Op::Concat => {
+                // a value with 20 digits cannot be the suffix of a concatenation that fits into a `u64`.
+                let base = 10u64.checked_pow(value.digit_count())?;
+                (target % base == value).then(|| target / base)
+            }
+        }
+    }
+
+    fn absorbs(self, target: u64, value: u64) -> bool {
+        self == Op::Mul && value == 0 && target == 0
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+            Op::Concat => "||",
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub target: u64,
+    pub numbers: Vec<u64>,
+}
+
+impl Equation {
+    /// Finds operators that, inserted between the numbers, produce the target.
+    pub fn solve<O: Operator>(&self, ops: &[O]) -> Option<Vec<O>> {
+        let mut found = Vec::with_capacity(self.numbers.len().saturating_sub(1));
+        solve_backwards(self.target, &self.numbers, ops, &mut found).then_some(found)
+    }
+
+    pub fn is_solvable<O: Operator>(&self, ops: &[O]) -> bool {
+        let mut found = Vec::new();
+        solve_backwards(self.target, &self.numbers, ops, &mut found)
+    }
+
+    /// Renders a solution, e.g. `292 = 11 + 6 * 16 + 20`.
+    pub fn expression<O: Operator>(&self, ops: &[O]) -> Option<String> {
+        let solution = self.solve(ops)?;
+        let mut out = format!("{} = {}", self.target, self.numbers.first()?);
+        for (op, n) in solution.iter().zip(&self.numbers[1..]) {
+            out.push_str(&format!(" {} {n}", op.symbol()));
+        }
+        Some(out)
+    }
+}
+
+/// Undoes the last operator for every candidate and recurses on the remaining numbers.
+/// Pushes the operators of a solution to `found` in left-to-right order.
+fn solve_backwards<O: Operator>(
+    target: u64,
+    numbers: &[u64],
+    ops: &[O],
+    found: &mut Vec<O>,
+) -> bool {
+    match numbers {
+        [] => false,
+        [first] => *first == target,
+        [rest @ .., last] => ops.iter().any(|&op| {
+            let solved = if op.absorbs(target, *last) {
+                solve_forwards(rest, ops, found)
+            } else {
+                op.invert(target, *last)
+                    .is_some_and(|acc| solve_backwards(acc, rest, ops, found))
+            };
+            if solved {
+                found.push(op);
+            }
+            solved
+        }),
+    }
+}
+
+/// Finds operators for which the numbers evaluate to any value without overflowing.
+/// Keeps the smallest intermediate result, which is enough for operators that grow with the accumulated value.
+fn solve_forwards<O: Operator>(numbers: &[u64], ops: &[O], found: &mut Vec<O>) -> bool {
+    let Some((first, rest)) = numbers.split_first() else {
+        return false;
+    };
+
+    let mut chosen = Vec::with_capacity(rest.len());
+    let mut acc = *first;
+    for &n in rest {
+        let Some((next, op)) = ops
+            .iter()
+            .filter_map(|&op| Some((op.apply(acc, n)?, op)))
+            .min_by_key(|(next, _)| *next)
+        else {
+            return false;
+        };
+        acc = next;
+        chosen.push(op);
+    }
+    found.extend(chosen);
+    true
+}
+
+/// Evaluates the numbers with the given operators left to right.
+pub fn evaluate<O: Operator>(numbers: &[u64], ops: &[O]) -> Option<u64> {
+    let (first, rest) = numbers.split_first()?;
+    rest.iter()
+        .zip(ops)
+        .try_fold(*first, |acc, (n, op)| op.apply(acc, *n))
+}
+
+/// Sum of the targets of all solvable equations, checked in parallel on all available cores.
+pub fn calibration_total<O: Operator + Sync>(equations: &[Equation], ops: &[O]) -> u64 {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = equations.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = equations
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter(|e| e.is_solvable(ops))
+                        .map(|e| e.target)
+                        .sum::<u64>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("solver thread panicked"))
+            .sum()
+    })
+}
+
+impl FromStr for Equation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, numbers) = key_values(s)?;
+        Ok(Self { target, numbers })
+    }
+}
+
+impl Display for Equation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.target)?;
+        for n in &self.numbers {
+            write!(f, " {n}")?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{calibration_total, evaluate, Equation, Op, Operator, ADD_MUL, ADD_MUL_CONCAT};
+    #[allow(unused_imports)]
+    use crate::random::Rng;
+
+    #[allow(dead_code)]
+    fn equation(s: &str) -> Equation {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn inverts_operators() {
+        assert_eq!(Op::Concat.apply(12, 345), Some(12345));
+        assert_eq!(Op::Concat.invert(12345, 345), Some(12));
+        assert_eq!(Op::Concat.invert(12345, 45), Some(123));
+        assert_eq!(Op::Concat.invert(12345, 35), None);
+        assert_eq!(Op::Concat.apply(1, 0), Some(10));
+        assert_eq!(Op::Concat.invert(10, 0), Some(1));
+        assert_eq!(Op::Mul.invert(12, 5), None);
+        assert_eq!(Op::Add.invert(3, 5), None);
+    }
+
+    #[test]
+    fn finds_expressions() {
+        assert_eq!(
+            equation("292: 11 6 16 20").expression(ADD_MUL).as_deref(),
+            Some("292 = 11 + 6 * 16 + 20")
+        );
+        assert_eq!(
+            equation("7290: 6 8 6 15")
+                .expression(ADD_MUL_CONCAT)
+                .as_deref(),
+            Some("7290 = 6 * 8 || 6 * 15")
+        );
+        assert_eq!(equation("83: 17 5").solve(ADD_MUL_CONCAT), None);
+    }
+
+    #[test]
+    fn multiplies_by_zero() {
+        assert!(equation("0: 5 0").is_solvable(ADD_MUL));
+        assert_eq!(
+            equation("0: 3 4 0").expression(ADD_MUL).as_deref(),
+            Some("0 = 3 + 4 * 0")
+        );
+        assert_eq!(equation("8: 3 4 0").solve(ADD_MUL), None);
+        assert!(equation("0: 0").is_solvable(ADD_MUL));
+    }
+
+    #[test]
+    fn sums_solvable_equations() {
+        let equations: Vec<Equation> = ["190: 10 19", "3267: 81 40 27", "83: 17 5", "156: 15 6"]
+            .into_iter()
+            .map(equation)
+            .collect();
+        assert_eq!(calibration_total(&equations, ADD_MUL), 3457);
+        assert_eq!(calibration_total(&equations, ADD_MUL_CONCAT), 3613);
+    }
+
+    #[test]
+    fn handles_twenty_digit_operands() {
+        let value = 12_345_678_901_234_567_890;
+        assert_eq!(Op::Concat.invert(value, value), None);
+        assert_eq!(Op::Concat.apply(1, value), None);
+        assert_eq!(
+            equation("12345678901234567890: 1 12345678901234567890").solve(ADD_MUL_CONCAT),
+            Some(vec![Op::Mul])
+        );
+    }
+
+    /// Compares the backwards search with evaluating every operator combination.
+    #[test]
+    fn matches_exhaustive_search() {
+        let mut rng = Rng::new(0x2024);
+
+        for _ in 0..500 {
+            let len = 1 + rng.below(6) as usize;
+            let numbers: Vec<u64> = (0..len).map(|_| rng.below(30)).collect();
+
+            // a reachable target for half of the cases, an arbitrary one otherwise.
+            let ops: Vec<Op> = (1..len).map(|_| *rng.pick(ADD_MUL_CONCAT)).collect();
+            let target = if rng.chance(50) {
+                evaluate(&numbers, &ops).unwrap()
+            } else {
+                rng.below(10_000)
+            };
+
+            let equation = Equation { target, numbers };
+            let exhaustive = (0..3usize.pow(len as u32 - 1)).any(|mut i| {
+                let ops: Vec<Op> = (1..len)
+                    .map(|_| {
+                        let op = ADD_MUL_CONCAT[i % 3];
+                        i /= 3;
+                        op
+                    })
+                    .collect();
+                evaluate(&equation.numbers, &ops) == Some(target)
+            });
+
+            let solution = equation.solve(ADD_MUL_CONCAT);
+            assert_eq!(solution.is_some(), exhaustive, "{equation}");
+            if let Some(ops) = solution {
+                assert_eq!(evaluate(&equation.numbers, &ops), Some(target));
+            }
+        }
+    }
+}
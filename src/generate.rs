@@ -0,0 +1,430 @@
+/// Synthetic puzzle inputs for stress tests, since real inputs must not be committed.
+/// Generated inputs follow the format and the constraints of real inputs, and default to their size,
+/// so timings on them are comparable. `cargo gen <day>` writes them to `data/generated/inputs`.
+use crate::bitgrid::BitGrid;
+use crate::direction::Direction;
+use crate::grid::{Coord, Grid};
+use crate::math::Integer;
+use crate::random::Rng;
+use crate::template::Day;
+use crate::walker::Walker;
+
+/// The input generator of a day.
+#[derive(Debug)]
+pub struct Generator {
+    pub day: u8,
+    /// What `size` counts, e.g. lines or the rows and columns of a square grid.
+    pub unit: &'static str,
+    /// The size of real inputs.
+    pub default_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (self.generate)(rng, size)
+    }
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        unit: "lines",
+        default_size: 1000,
+        generate: location_lists,
+    },
+    Generator {
+        day: 2,
+        unit: "reports",
+        default_size: 1000,
+        generate: reports,
+    },
+    Generator {
+        day: 3,
+        unit: "characters",
+        default_size: 18000,
+        generate: corrupted_memory,
+    },
+    Generator {
+        day: 4,
+        unit: "rows and columns",
+        default_size: 140,
+        generate: word_search,
+    },
+    Generator {
+        day: 5,
+        unit: "updates",
+        default_size: 200,
+        generate: print_queue,
+    },
+    Generator {
+        day: 6,
+        unit: "rows and columns",
+        default_size: 130,
+        generate: guard_map,
+    },
+    Generator {
+        day: 7,
+        unit: "equations",
+        default_size: 850,
+        generate: calibration_equations,
+    },
+    Generator {
+        day: 8,
+        unit: "rows and columns",
+        default_size: 50,
+        generate: antenna_map,
+    },
+];
+
+/// Looks up the generator of a day.
+pub fn find(day: Day) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day.into_inner())
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn grid_to_string(grid: &Grid<char>) -> String {
+    lines(grid.rows().map(|row| row.iter().collect()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Day 1: two columns of five-digit location ids. About half of the right column repeats ids of the left one.
+pub fn location_lists(rng: &mut Rng, size: usize) -> String {
+    let left: Vec<u64> = (0..size).map(|_| rng.range(10000..100000)).collect();
+    lines(left.iter().map(|a| {
+        let b = if rng.chance(50) {
+            *rng.pick(&left)
+        } else {
+            rng.range(10000..100000)
+        };
+        format!("{a}   {b}")
+    }))
+}
+
+/// Day 2: reports of 5 to 8 levels between 1 and 99. Most reports are safe or become safe without
+/// one level, the others change direction or jump by more than three.
+pub fn reports(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let len = rng.range(5..9) as usize;
+        let direction = if rng.chance(50) { 1 } else { -1 };
+        let mut levels = vec![rng.range(25..75) as i64];
+        while levels.len() < len {
+            let step = rng.range(1..4) as i64 * direction;
+            levels.push(levels[levels.len() - 1] + step);
+        }
+        for _ in 0..rng.below(3) {
+            let i = rng.index(len);
+            levels[i] += rng.range(1..6) as i64 * if rng.chance(50) { 1 } else { -1 };
+        }
+        levels
+            .iter()
+            .map(|&level| level.clamp(1, 99).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }))
+}
+
+/// Day 3: lines of about 3000 characters of noise, with `mul(a,b)` instructions of one to three digit
+/// numbers, corrupted instructions and `do()`/`don't()`.
+pub fn corrupted_memory(rng: &mut Rng, size: usize) -> String {
+    const NOISE: &[u8] = b"!@#$%^&*()[]{}<>,'?;:+-_ ~/why";
+    const WORDS: &[&str] = &["what()", "how()", "from()", "when()", "select()", "who()"];
+    const LINE_LENGTH: usize = 3000;
+
+    let number = |rng: &mut Rng| rng.range(1..1000);
+    let line_count = size.div_ceil(LINE_LENGTH).max(1);
+    lines((0..line_count).map(|i| {
+        let target = (size - i * LINE_LENGTH).min(LINE_LENGTH);
+        let mut line = String::new();
+        while line.len() < target {
+            let token = match rng.below(20) {
+                0..=4 => format!("mul({},{})", number(rng), number(rng)),
+                5 => format!("mul({},{}]", number(rng), number(rng)),
+                6 => format!("mul ({},{})", number(rng), number(rng)),
+                7 => format!("mul({}, {})", number(rng), number(rng)),
+                8 => format!("mul({},{}", number(rng), number(rng)),
+                9 => "do()".to_string(),
+                10 => "don't()".to_string(),
+                11 => rng.pick(WORDS).to_string(),
+                _ => (*rng.pick(NOISE) as char).to_string(),
+            };
+            line.push_str(&token);
+        }
+        line
+    }))
+}
+
+/// Day 4: a square grid of the letters `XMAS`, with additional words and X-shaped `MAS` crosses
+/// planted in random directions, as real inputs contain more of them than random letters.
+pub fn word_search(rng: &mut Rng, size: usize) -> String {
+    let letters = ['X', 'M', 'A', 'S'];
+    let cells = (0..size * size).map(|_| *rng.pick(&letters)).collect();
+    let Some(mut grid) = Grid::from_vec(size, cells) else {
+        return String::new();
+    };
+
+    for _ in 0..size * size / 10 {
+        let start = grid.coord_of(rng.index(grid.len())).unwrap();
+        let direction = *rng.pick(&Direction::ALL);
+        let cells: Vec<_> = std::iter::once(start)
+            .chain(grid.ray(start, direction).take(3))
+            .collect();
+        if cells.len() == 4 {
+            for (cell, letter) in cells.into_iter().zip("XMAS".chars()) {
+                grid[cell] = letter;
+            }
+        }
+    }
+
+    for _ in 0..size * size / 12 {
+        let center = grid.coord_of(rng.index(grid.len())).unwrap();
+        let corners: Vec<_> = Direction::DIAGONAL
+            .iter()
+            .filter_map(|&d| grid.offset(center, d))
+            .collect();
+        if corners.len() == 4 {
+            // the two `M` are on neighbouring corners, so both diagonals read `MAS`.
+            let first = rng.index(4);
+            for (i, corner) in corners.into_iter().enumerate() {
+                grid[corner] = if (i + 4 - first) % 4 < 2 { 'M' } else { 'S' };
+            }
+            grid[center] = 'A';
+        }
+    }
+
+    grid_to_string(&grid)
+}
+
+/// Day 5: a rule for every pair of 49 two-digit pages and updates of an odd number of pages.
+///
+/// Like in real inputs, the rules contain cycles: the pages sit on a circle and every page precedes
+/// the 24 pages after it. An update takes its pages from half of the circle, where the rules agree
+/// with a single order. About half of the updates are in that order.
+pub fn print_queue(rng: &mut Rng, size: usize) -> String {
+    const PAGES: usize = 49;
+    const REACH: usize = PAGES / 2;
+
+    let mut pages: Vec<u64> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(PAGES);
+
+    let mut rules: Vec<String> = (0..PAGES)
+        .flat_map(|i| (1..=REACH).map(move |d| (i, (i + d) % PAGES)))
+        .map(|(a, b)| format!("{}|{}", pages[a], pages[b]))
+        .collect();
+    rng.shuffle(&mut rules);
+
+    let updates = (0..size).map(|_| {
+        let start = rng.index(PAGES);
+        let mut arc: Vec<usize> = (0..=REACH).map(|d| (start + d) % PAGES).collect();
+        let len = 5 + 2 * rng.index(10);
+        rng.shuffle(&mut arc);
+        let mut update: Vec<usize> = arc[..len].to_vec();
+        if rng.chance(50) {
+            // positions along the arc.
+            update.sort_by_key(|&p| (p + PAGES - start) % PAGES);
+        }
+        update
+            .iter()
+            .map(|&p| pages[p].to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    });
+
+    let mut out = lines(rules);
+    out.push('\n');
+    out.push_str(&lines(updates));
+    out
+}
+
+/// Day 6: a square map with a guard facing up, who walks off the map without additional obstructions.
+///
+/// Like in real inputs, the guard covers a large part of the map. Its path is an irregular spiral
+/// outwards, where every run is longer than the run two turns before, so the path never crosses itself.
+/// Further obstructions are scattered over cells the guard never visits.
+pub fn guard_map(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let third = (size / 3).max(1);
+    loop {
+        let mut grid = Grid::new(size, size, '.');
+        let mut visited = BitGrid::for_grid(&grid);
+        let start = Coord::new(
+            (size / 3 + rng.index(third)).min(size - 1),
+            (size / 3 + rng.index(third)).min(size - 1),
+        );
+        let (mut position, mut heading) = (start, Direction::N);
+        visited.insert(position);
+
+        // lengths of the last two runs, indexed by the parity of the run.
+        let mut runs = [rng.range(1..5), rng.range(1..5)];
+        'walk: for turn in 0.. {
+            let run = runs[turn % 2];
+            for _ in 0..run {
+                let Some(next) = grid.offset(position, heading) else {
+                    break 'walk;
+                };
+                position = next;
+                visited.insert(position);
+            }
+            match grid.offset(position, heading) {
+                Some(ahead) if !visited.contains(ahead) => grid[ahead] = '#',
+                Some(_) => {}
+                None => break,
+            }
+            heading = heading.turn_right();
+            // the obstruction sticks out of the run, the next ring has to pass it at a distance.
+            runs[turn % 2] = run + rng.range(2..5);
+        }
+
+        for coord in grid.coords().collect::<Vec<_>>() {
+            if !visited.contains(coord) && grid[coord] == '.' && rng.chance(4) {
+                grid[coord] = '#';
+            }
+        }
+        grid[start] = '^';
+
+        let leaves = !Walker::new(&grid, |c| *c == '#')
+            .walk(start, Direction::N)
+            .is_loop();
+        if leaves {
+            return grid_to_string(&grid);
+        }
+    }
+}
+
+/// Day 7: equations of 3 to 12 numbers below 1000. About half of the test values are the result of
+/// combining the numbers with `+`, `*` and `||`, the others are off by a little.
+pub fn calibration_equations(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| loop {
+        let numbers: Vec<u64> = (0..rng.range(3..13))
+            .map(|_| match rng.below(3) {
+                0 => rng.range(1..10),
+                1 => rng.range(10..100),
+                _ => rng.range(100..1000),
+            })
+            .collect();
+        let value = numbers[1..]
+            .iter()
+            .try_fold(numbers[0], |acc, &n| match rng.below(3) {
+                0 => acc.checked_add(n),
+                1 => acc.checked_mul(n),
+                _ => acc.checked_concat(n),
+            })
+            .filter(|&value| value < 1_000_000_000_000_000);
+        let Some(mut value) = value else {
+            continue;
+        };
+        if rng.chance(50) {
+            value += rng.range(1..10);
+        }
+        let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        break format!("{value}: {}", numbers.join(" "));
+    }))
+}
+
+/// Day 8: a square map with groups of three to five antennas per frequency,
+/// using digits and letters as frequencies.
+pub fn antenna_map(rng: &mut Rng, size: usize) -> String {
+    let mut frequencies: Vec<char> = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+    rng.shuffle(&mut frequencies);
+    let count = (size * size / 70).clamp(1, frequencies.len());
+
+    let mut grid = Grid::new(size, size, '.');
+    let mut free: Vec<usize> = (0..grid.len()).collect();
+    rng.shuffle(&mut free);
+    let mut free = free.into_iter();
+    for &frequency in &frequencies[..count] {
+        for _ in 0..rng.range(3..6) {
+            let Some(cell) = free.next().and_then(|i| grid.coord_of(i)) else {
+                break;
+            };
+            grid[cell] = frequency;
+        }
+    }
+    grid_to_string(&grid)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{find, GENERATORS};
+    #[allow(unused_imports)]
+    use crate::{
+        direction::Direction, grid::Grid, ordering::OrderingRules, random::Rng, template::Day,
+        walker::Walker,
+    };
+
+    #[allow(dead_code)]
+    fn generate(day: u8, size: usize) -> String {
+        find(Day::new(day).unwrap())
+            .unwrap()
+            .generate(&mut Rng::new(0x2024), size)
+    }
+
+    #[test]
+    fn is_reproducible() {
+        for generator in GENERATORS {
+            let generate = |seed| generator.generate(&mut Rng::new(seed), 20);
+            assert_eq!(generate(1), generate(1), "day {}", generator.day);
+            assert!(generate(1).ends_with('\n'));
+        }
+        assert!(find(Day::new(25).unwrap()).is_none());
+    }
+
+    #[test]
+    fn generates_sized_inputs() {
+        assert_eq!(generate(1, 1000).lines().count(), 1000);
+        assert_eq!(generate(2, 10).lines().count(), 10);
+        assert!((18000..18100).contains(&generate(3, 18000).len()));
+        assert_eq!(generate(7, 30).lines().count(), 30);
+
+        let grid: Grid<char> = generate(4, 140).parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (140, 140));
+        assert!(grid.cells().iter().all(|c| "XMAS".contains(*c)));
+    }
+
+    #[test]
+    fn keeps_updates_consistent_with_rules() {
+        let input = generate(5, 200);
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+        let rules: OrderingRules<u32> = rules.parse().unwrap();
+        assert_eq!(rules.len(), 49 * 24);
+
+        let updates: Vec<Vec<u32>> = updates
+            .lines()
+            .map(|l| l.split(',').map(|p| p.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(updates.len(), 200);
+        let ordered = updates.iter().filter(|u| rules.is_ordered(u)).count();
+        assert!((50..150).contains(&ordered));
+        for mut update in updates {
+            assert_eq!(update.len() % 2, 1);
+            rules.sort(&mut update);
+            assert!(rules.is_ordered(&update));
+        }
+    }
+
+    #[test]
+    fn lets_the_guard_leave() {
+        for seed in 0..20 {
+            let grid: Grid<char> = GENERATORS[5]
+                .generate(&mut Rng::new(seed), 30)
+                .parse()
+                .unwrap();
+            let start = grid.find(|c| *c == '^').unwrap();
+            assert!(!Walker::new(&grid, |c| *c == '#')
+                .walk(start, Direction::N)
+                .is_loop());
+        }
+
+        let antennas: Grid<char> = generate(8, 50).parse().unwrap();
+        let count = antennas.cells().iter().filter(|c| **c != '.').count();
+        assert!((35 * 3..=35 * 5).contains(&count));
+    }
+}
@@ -0,0 +1,592 @@
+/// Graph searches over [`Grid`]s and explicit adjacency maps: BFS, DFS, Dijkstra and A*,
+/// plus connected components and flood-filled regions of a grid.
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::direction::Direction;
+use crate::grid::{Coord, Grid};
+
+/// A directed graph with weighted edges. Undirected graphs list every edge in both directions.
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    /// Nodes reachable from `node` in one move, with the cost of the move.
+    fn successors(&self, node: Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A [`Grid`] seen as a graph: cells are nodes, and a move leads to a neighbouring passable cell.
+///
+/// ```
+/// # use advent_of_code::{graph::{bfs, GridGraph}, grid::{Coord, Grid}};
+/// let grid: Grid<char> = "..#\n#..\n...".parse().unwrap();
+/// let graph = GridGraph::new(&grid, |c| *c != '#');
+/// let paths = bfs(&graph, Coord::new(0, 0));
+/// assert_eq!(paths.distance(Coord::new(2, 2)), Some(4));
+/// ```
+pub struct GridGraph<'a, T, P, C = fn(Coord, Coord) -> u64> {
+    grid: &'a Grid<T>,
+    passable: P,
+    cost: C,
+    moves: &'static [Direction],
+}
+
+impl<'a, T, P> GridGraph<'a, T, P>
+where
+    P: Fn(&T) -> bool,
+{
+    /// Orthogonal moves onto cells that satisfy `passable`, each costing 1.
+    pub fn new(grid: &'a Grid<T>, passable: P) -> Self {
+        Self {
+            grid,
+            passable,
+            cost: |_, _| 1,
+            moves: &Direction::ORTHOGONAL,
+        }
+    }
+}
+
+impl<'a, T, P, C> GridGraph<'a, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn(Coord, Coord) -> u64,
+{
+    /// Replaces the cost of moving from one cell to the next.
+    pub fn with_cost<D>(self, cost: D) -> GridGraph<'a, T, P, D>
+    where
+        D: Fn(Coord, Coord) -> u64,
+    {
+        GridGraph {
+            grid: self.grid,
+            passable: self.passable,
+            cost,
+            moves: self.moves,
+        }
+    }
+
+    /// Replaces the directions a move can take, e.g. [`Direction::ALL`] to allow diagonal moves.
+    pub fn with_moves(mut self, moves: &'static [Direction]) -> Self {
+        self.moves = moves;
+        self
+    }
+
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+}
+
+impl<T, P, C> Graph for GridGraph<'_, T, P, C>
+where
+    P: Fn(&T) -> bool,
+    C: Fn(Coord, Coord) -> u64,
+{
+    type Node = Coord;
+
+    fn successors(&self, node: Coord) -> impl Iterator<Item = (Coord, u64)> {
+        self.moves
+            .iter()
+            .filter_map(move |&direction| self.grid.offset(node, direction))
+            .filter(|&next| (self.passable)(&self.grid[next]))
+            .map(move |next| (next, (self.cost)(node, next)))
+    }
+}
+
+/// A graph given by the outgoing edges of every node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjacencyMap<N: Eq + Hash> {
+    edges: HashMap<N, Vec<(N, u64)>>,
+}
+
+impl<N: Eq + Hash> Default for AdjacencyMap<N> {
+    fn default() -> Self {
+        Self {
+            edges: HashMap::new(),
+        }
+    }
+}
+
+impl<N> AdjacencyMap<N>
+where
+    N: Copy + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, cost: u64) {
+        self.edges.entry(from).or_default().push((to, cost));
+    }
+
+    /// Adds an edge in both directions.
+    pub fn add_undirected_edge(&mut self, a: N, b: N, cost: u64) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+
+    /// Nodes with outgoing edges.
+    pub fn nodes(&self) -> impl Iterator<Item = N> + '_ {
+        self.edges.keys().copied()
+    }
+}
+
+impl<N> Graph for AdjacencyMap<N>
+where
+    N: Copy + Eq + Hash,
+{
+    type Node = N;
+
+    fn successors(&self, node: N) -> impl Iterator<Item = (N, u64)> {
+        self.edges.get(&node).into_iter().flatten().copied()
+    }
+}
+
+/// Builds a graph from `(from, to, cost)` edges.
+impl<N> FromIterator<(N, N, u64)> for AdjacencyMap<N>
+where
+    N: Copy + Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (N, N, u64)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (from, to, cost) in iter {
+            map.add_edge(from, to, cost);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The nodes reached by a search, with their distance from the start and the tree of predecessors.
+#[derive(Debug, Clone)]
+pub struct Paths<N> {
+    order: Vec<N>,
+    distances: HashMap<N, u64>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N> Paths<N>
+where
+    N: Copy + Eq + Hash,
+{
+    fn new(start: N) -> Self {
+        Self {
+            order: Vec::new(),
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// Reached nodes in the order the search visited them, starting with the start node.
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn contains(&self, node: N) -> bool {
+        self.distances.contains_key(&node)
+    }
+
+    /// Distance from the start: the number of moves for [`bfs`] and [`dfs`], the total cost for [`dijkstra`].
+    pub fn distance(&self, node: N) -> Option<u64> {
+        self.distances.get(&node).copied()
+    }
+
+    /// The node before `node` on its path from the start.
+    pub fn predecessor(&self, node: N) -> Option<N> {
+        self.predecessors.get(&node).copied()
+    }
+
+    /// The path from the start to `node`, both included.
+    pub fn path_to(&self, node: N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path: Vec<N> =
+            std::iter::successors(Some(node), |n| self.predecessor(*n)).collect();
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search from `start`, ignoring move costs.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    let mut paths = Paths::new(start);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        paths.order.push(node);
+        let distance = paths.distances[&node] + 1;
+        for (next, _) in graph.successors(node) {
+            if let Entry::Vacant(entry) = paths.distances.entry(next) {
+                entry.insert(distance);
+                paths.predecessors.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Depth-first search from `start`, ignoring move costs. Distances are depths in the search tree.
+pub fn dfs<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    let mut paths = Paths::new(start);
+    let mut seen = HashSet::new();
+    let mut stack = vec![(start, None)];
+
+    while let Some((node, predecessor)) = stack.pop() {
+        if !seen.insert(node) {
+            continue;
+        }
+        if let Some(predecessor) = predecessor {
+            let depth = paths.distances[&predecessor] + 1;
+            paths.distances.insert(node, depth);
+            paths.predecessors.insert(node, predecessor);
+        }
+        paths.order.push(node);
+
+        // pushed in reverse, so successors are visited in the order the graph lists them.
+        let successors: Vec<_> = graph.successors(node).collect();
+        for (next, _) in successors.into_iter().rev() {
+            if !seen.contains(&next) {
+                stack.push((next, Some(node)));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Shortest paths from `start` to every reachable node by total move cost.
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node) -> Paths<G::Node> {
+    let mut paths = Paths::new(start);
+    let mut settled = HashSet::new();
+    // nodes need not be `Ord`, so the heap holds indices into `queued`.
+    let mut queued = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+
+    while let Some(Reverse((distance, index))) = heap.pop() {
+        let node = queued[index];
+        if !settled.insert(node) {
+            continue;
+        }
+        paths.order.push(node);
+
+        for (next, cost) in graph.successors(node) {
+            let candidate = distance + cost;
+            if paths.distance(next).is_none_or(|d| candidate < d) {
+                paths.distances.insert(next, candidate);
+                paths.predecessors.insert(next, node);
+                heap.push(Reverse((candidate, queued.len())));
+                queued.push(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Cheapest path from `start` to a node satisfying `is_goal`, with its total cost.
+/// `heuristic` estimates the remaining cost and must never overestimate it; with `|_| 0` this is Dijkstra.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut is_goal: impl FnMut(G::Node) -> bool,
+    mut heuristic: impl FnMut(G::Node) -> u64,
+) -> Option<(u64, Vec<G::Node>)> {
+    let mut paths = Paths::new(start);
+    let mut settled = HashSet::new();
+    let mut queued = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, 0))]);
+
+    while let Some(Reverse((_, distance, index))) = heap.pop() {
+        let node = queued[index];
+        if !settled.insert(node) {
+            continue;
+        }
+        if is_goal(node) {
+            return Some((distance, paths.path_to(node)?));
+        }
+
+        for (next, cost) in graph.successors(node) {
+            let candidate = distance + cost;
+            if paths.distance(next).is_none_or(|d| candidate < d) {
+                paths.distances.insert(next, candidate);
+                paths.predecessors.insert(next, node);
+                heap.push(Reverse((
+                    candidate + heuristic(next),
+                    candidate,
+                    queued.len(),
+                )));
+                queued.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Groups `nodes` by the nodes reachable from them, in the order of their first node.
+/// Meant for undirected graphs; in a directed graph, the first node of a group decides what it contains.
+pub fn connected_components<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = bfs(graph, node).order;
+        seen.extend(component.iter().copied());
+        components.push(component);
+    }
+
+    components
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Orthogonally connected cells of a grid that hold the same value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The cells of the region in the order they were filled, starting with the seed cell.
+    pub cells: Vec<Coord>,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges that border a cell outside the region or the grid edge.
+    pub fn perimeter(&self) -> usize {
+        let cells: HashSet<Coord> = self.cells.iter().copied().collect();
+        self.cells
+            .iter()
+            .flat_map(|&cell| Direction::ORTHOGONAL.map(|d| cell.offset(d)))
+            .filter(|n| n.is_none_or(|n| !cells.contains(&n)))
+            .count()
+    }
+
+    /// Number of straight fence sections around the region, including the ones around holes.
+    /// A polygon has as many sides as corners, so this counts the corners of every cell.
+    pub fn sides(&self) -> usize {
+        let cells: HashSet<Coord> = self.cells.iter().copied().collect();
+        let inside = |cell: Coord, direction: Direction| {
+            cell.offset(direction).is_some_and(|n| cells.contains(&n))
+        };
+
+        self.cells
+            .iter()
+            .map(|&cell| {
+                Direction::ORTHOGONAL
+                    .into_iter()
+                    .filter(|&a| {
+                        let b = a.turn_right();
+                        let (a_in, b_in) = (inside(cell, a), inside(cell, b));
+                        let outer = !a_in && !b_in;
+                        let inner = a_in && b_in && !inside(cell, a.turn_right_45());
+                        outer || inner
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// The region of cells connected to `start` that hold the same value.
+pub fn flood_fill<T: PartialEq>(grid: &Grid<T>, start: Coord) -> Region {
+    let value = &grid[start];
+    let graph = GridGraph::new(grid, |c| c == value);
+    Region {
+        cells: bfs(&graph, start).order,
+    }
+}
+
+/// Splits the grid into regions of equal, orthogonally connected cells, in row-major order of their first cell.
+pub fn regions<T: PartialEq>(grid: &Grid<T>) -> Vec<Region> {
+    let mut seen = vec![false; grid.len()];
+    let mut regions = Vec::new();
+
+    for (i, coord) in grid.coords().enumerate() {
+        if seen[i] {
+            continue;
+        }
+        let region = flood_fill(grid, coord);
+        for cell in &region.cells {
+            if let Some(index) = grid.index_of(*cell) {
+                seen[index] = true;
+            }
+        }
+        regions.push(region);
+    }
+
+    regions
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{
+        astar, bfs, connected_components, dfs, dijkstra, flood_fill, regions, AdjacencyMap, Graph,
+        GridGraph,
+    };
+    #[allow(unused_imports)]
+    use crate::random::Rng;
+    #[allow(unused_imports)]
+    use crate::{
+        direction::Direction,
+        grid::{Coord, Grid},
+    };
+
+    #[allow(dead_code)]
+    fn maze() -> Grid<char> {
+        ["...#", ".#..", ".#.#", "...."].join("\n").parse().unwrap()
+    }
+
+    #[test]
+    fn searches_grids() {
+        let grid = maze();
+        let graph = GridGraph::new(&grid, |c| *c == '.');
+        let paths = bfs(&graph, Coord::new(0, 0));
+        assert_eq!(paths.distance(Coord::new(3, 1)), Some(4));
+        assert_eq!(
+            paths.path_to(Coord::new(3, 1)),
+            Some(vec![
+                Coord::new(0, 0),
+                Coord::new(1, 0),
+                Coord::new(2, 0),
+                Coord::new(2, 1),
+                Coord::new(3, 1)
+            ])
+        );
+        assert_eq!(paths.len(), 12);
+        assert!(!paths.contains(Coord::new(1, 1)));
+
+        let diagonal = GridGraph::new(&grid, |c| *c == '.').with_moves(&Direction::ALL);
+        assert_eq!(
+            bfs(&diagonal, Coord::new(0, 0)).distance(Coord::new(3, 3)),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn searches_depth_first() {
+        let graph: AdjacencyMap<u8> = [(1, 2, 1), (1, 3, 1), (2, 4, 1), (3, 4, 1)]
+            .into_iter()
+            .collect();
+        let paths = dfs(&graph, 1);
+        assert_eq!(paths.order(), &[1, 2, 4, 3]);
+        assert_eq!(paths.distance(4), Some(2));
+        assert_eq!(paths.path_to(3), Some(vec![1, 3]));
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let graph: AdjacencyMap<char> = [
+            ('a', 'b', 7),
+            ('a', 'c', 2),
+            ('c', 'b', 3),
+            ('b', 'd', 1),
+            ('c', 'd', 8),
+        ]
+        .into_iter()
+        .collect();
+        let paths = dijkstra(&graph, 'a');
+        assert_eq!(paths.distance('d'), Some(6));
+        assert_eq!(paths.path_to('d'), Some(vec!['a', 'c', 'b', 'd']));
+        assert_eq!(
+            astar(&graph, 'a', |n| n == 'd', |_| 0),
+            Some((6, vec!['a', 'c', 'b', 'd']))
+        );
+        assert_eq!(astar(&graph, 'd', |n| n == 'a', |_| 0), None);
+    }
+
+    #[test]
+    fn groups_components() {
+        let mut graph = AdjacencyMap::new();
+        graph.add_undirected_edge(1, 2, 1);
+        graph.add_undirected_edge(3, 4, 1);
+        graph.add_undirected_edge(4, 5, 1);
+        let components = connected_components(&graph, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(components, vec![vec![1, 2], vec![3, 4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn measures_regions() {
+        let grid: Grid<char> = ["AAAA", "BBCD", "BBCC", "EEEC"].join("\n").parse().unwrap();
+        let regions = regions(&grid);
+        let measures: Vec<_> = regions
+            .iter()
+            .map(|r| (grid[r.cells[0]], r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            measures,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4)
+            ]
+        );
+
+        // a region with holes also has fences on the inside.
+        let grid: Grid<char> = ["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]
+            .join("\n")
+            .parse()
+            .unwrap();
+        let outer = flood_fill(&grid, Coord::new(0, 0));
+        assert_eq!(
+            (outer.area(), outer.perimeter(), outer.sides()),
+            (21, 36, 20)
+        );
+    }
+
+    /// Compares A* with a Manhattan heuristic to Dijkstra on random weighted grids.
+    #[test]
+    fn astar_matches_dijkstra() {
+        let mut rng = Rng::new(0x2024);
+
+        for _ in 0..200 {
+            let (width, height) = (2 + rng.below(10) as usize, 2 + rng.below(10) as usize);
+            let cells: Vec<u64> = (0..width * height).map(|_| rng.below(10)).collect();
+            let grid = Grid::from_vec(width, cells).unwrap();
+            // zeros are walls, other cells cost their value to enter.
+            let graph = GridGraph::new(&grid, |c| *c > 0).with_cost(|_, to| grid[to]);
+
+            let goal = Coord::new(width - 1, height - 1);
+            let start = Coord::new(0, 0);
+            let expected = dijkstra(&graph, start).distance(goal);
+            let found = astar(
+                &graph,
+                start,
+                |c| c == goal,
+                |c| ((goal.x - c.x) + (goal.y - c.y)) as u64,
+            );
+            assert_eq!(found.as_ref().map(|(cost, _)| *cost), expected);
+
+            if let Some((cost, path)) = found {
+                assert_eq!(path.first(), Some(&start));
+                assert_eq!(path.last(), Some(&goal));
+                let total: u64 = path[1..].iter().map(|c| grid[*c]).sum();
+                assert_eq!(total, cost);
+            }
+        }
+    }
+}
@@ -0,0 +1,514 @@
+/// A dense, row-major two-dimensional grid, as found in most puzzle inputs.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::direction::Direction;
+
+/// Position of a cell in a [`Grid`]. `x` is the column, `y` the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves the coordinate by a [`Direction`] or a `(dx, dy)` delta.
+    /// Returns `None` if the result would be negative.
+    pub fn offset(self, delta: impl Into<(isize, isize)>) -> Option<Self> {
+        let (dx, dy) = delta.into();
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of cells.
+///
+/// ```
+/// # use advent_of_code::grid::{Coord, Grid};
+/// let grid: Grid<char> = "ab\ncd".parse().unwrap();
+/// assert_eq!(grid[Coord::new(1, 0)], 'b');
+/// assert_eq!(grid.transpose().to_string(), "ac\nbd");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` × `height` cells with the value `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from row-major cells. Returns `None` if `cells` does not fill whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses a grid from puzzle text, converting every character with `f`.
+    /// Fails if the text is empty, if rows differ in length or if `f` rejects a character.
+    pub fn parse_with<E>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, GridError<E>> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|e| GridError::Cell(Coord::new(x, y), e))?);
+            }
+            let len = cells.len() - before;
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(GridError::Ragged {
+                        row: y,
+                        expected: w,
+                        found: len,
+                    })
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    /// Row-major index of `coord`, e.g. to key a flat lookup table.
+    pub fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.in_bounds(coord)
+            .then_some(coord.y * self.width + coord.x)
+    }
+
+    /// Inverse of [`Grid::index_of`].
+    pub fn coord_of(&self, index: usize) -> Option<Coord> {
+        (index < self.cells.len()).then(|| Coord::new(index % self.width, index / self.width))
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves `coord` by a [`Direction`] or a `(dx, dy)` delta, staying inside the grid.
+    pub fn offset(&self, coord: Coord, delta: impl Into<(isize, isize)>) -> Option<Coord> {
+        coord.offset(delta).filter(|c| self.in_bounds(*c))
+    }
+
+    /// Iterates every coordinate in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Coord::new(i % width, i / width))
+    }
+
+    /// Iterates every cell together with its coordinate in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    /// Orthogonal neighbours of `coord` that lie inside the grid.
+    pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.offset(coord, direction))
+    }
+
+    /// Orthogonal and diagonal neighbours of `coord` that lie inside the grid.
+    pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.offset(coord, direction))
+    }
+
+    /// Coordinates reached by repeatedly stepping `delta` from `start` (exclusive) until leaving the grid.
+    pub fn ray(
+        &self,
+        start: Coord,
+        delta: impl Into<(isize, isize)>,
+    ) -> impl Iterator<Item = Coord> + '_ {
+        let delta = delta.into();
+        std::iter::successors(self.offset(start, delta), move |c| self.offset(*c, delta))
+    }
+
+    /// Coordinate of the first cell in row-major order that satisfies `pred`.
+    pub fn find(&self, pred: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(pred)
+            .and_then(|i| self.coord_of(i))
+    }
+
+    /// Coordinates of all cells that satisfy `pred`, in row-major order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut pred: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Coord> + 'a {
+        self.iter().filter(move |(_, c)| pred(c)).map(|(c, _)| c)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // a grid without columns has no cells, so the chunk size only needs to be valid.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let len = if x < self.width { self.cells.len() } else { 0 };
+        self.cells[..len].iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals running down-right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.nonempty_size();
+        let left = (0..height).rev().map(|y| Coord::new(0, y));
+        let top = (1..width).map(|x| Coord::new(x, 0));
+        left.chain(top).map(|start| self.line(start, Direction::SE))
+    }
+
+    /// Diagonals running down-left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = self.nonempty_size();
+        let top = (0..width).map(|x| Coord::new(x, 0));
+        let right = (1..height).map(move |y| Coord::new(width - 1, y));
+        top.chain(right)
+            .map(|start| self.line(start, Direction::SW))
+    }
+
+    /// Width and height, or zero for both if the grid has no cells, so that every start of a line is in bounds.
+    fn nonempty_size(&self) -> (usize, usize) {
+        if self.cells.is_empty() {
+            (0, 0)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    fn line(&self, start: Coord, direction: Direction) -> impl Iterator<Item = &T> {
+        std::iter::once(start)
+            .chain(self.ray(start, direction))
+            .map(|c| &self[c])
+    }
+
+    /// Applies `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| Coord::new(y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(height, self.width, move |x, y| {
+            Coord::new(y, height - 1 - x)
+        })
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, width, move |x, y| Coord::new(width - 1 - y, x))
+    }
+
+    /// Builds a `width` × `height` grid whose cell at `(x, y)` is the cell of `self` at `source(x, y)`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> Coord) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|i| self[source(i % width, i / width)].clone())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        assert!(self.in_bounds(coord), "{coord} is outside of the grid");
+        &self.cells[coord.y * self.width + coord.x]
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        assert!(self.in_bounds(coord), "{coord} is outside of the grid");
+        &mut self.cells[coord.y * self.width + coord.x]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError<std::convert::Infallible>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Ok)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError<E> {
+    Empty,
+    /// A row has a different length than the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell parser rejected the character at a coordinate.
+    Cell(Coord, E),
+}
+
+impl<E: Display> Display for GridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row"
+            ),
+            GridError::Cell(coord, e) => write!(f, "invalid cell at {coord}: {e}"),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + Display> Error for GridError<E> {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Coord, Grid, GridError};
+    #[allow(unused_imports)]
+    use crate::direction::Direction;
+
+    #[allow(dead_code)]
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[allow(dead_code)]
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        lines.map(|l| l.collect()).collect()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(2, 1)], 'f');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn handles_zero_width() {
+        let grid = Grid::new(0, 3, '.');
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.column(0).count(), 0);
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+        assert_eq!(grid.to_string(), "");
+
+        let grid = Grid::new(3, 0, '.');
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("12\n3x", |c| c.to_digit(10).ok_or(c)),
+            Err(GridError::Cell(Coord::new(1, 1), 'x'))
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = grid();
+        let mut n4: Vec<Coord> = grid.neighbours4(Coord::new(0, 0)).collect();
+        n4.sort();
+        assert_eq!(n4, vec![Coord::new(0, 1), Coord::new(1, 0)]);
+        assert_eq!(grid.neighbours8(Coord::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = grid();
+        let ray: String = grid
+            .ray(Coord::new(0, 0), (1, 0))
+            .map(|c| grid[c])
+            .collect();
+        assert_eq!(ray, "bc");
+        assert_eq!(grid.ray(Coord::new(0, 0), (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = "#.#\n.#.".parse().unwrap();
+        assert_eq!(grid.find(|c| *c == '.'), Some(Coord::new(1, 0)));
+        assert_eq!(grid.find_all(|c| *c == '#').count(), 3);
+        assert_eq!(grid.find(|c| *c == 'x'), None);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(collect(grid.columns()), vec!["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn converts_indices() {
+        let grid = grid();
+        assert_eq!(grid.index_of(Coord::new(1, 1)), Some(4));
+        assert_eq!(grid.coord_of(4), Some(Coord::new(1, 1)));
+        assert_eq!(grid.coord_of(6), None);
+    }
+}
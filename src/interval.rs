@@ -0,0 +1,380 @@
+/// Sets of integers stored as sorted, disjoint half-open ranges, for puzzles that split and shift
+/// ranges far too large to enumerate.
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Range, Sub};
+
+/// An integer type that can bound a range.
+pub trait Endpoint: Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T> Endpoint for T where T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T> {}
+
+/// A set of values, stored as the smallest number of ranges that cover them.
+/// Ranges are half-open like `Range`, so `1..=3` is inserted as `1..4`.
+///
+/// ```
+/// # use advent_of_code::interval::IntervalSet;
+/// let mut set: IntervalSet<u64> = [1..4, 10..12].into_iter().collect();
+/// set.insert(4..6);
+/// set.remove(2..3);
+/// assert_eq!(set.ranges(), &[1..2, 3..6, 10..12]);
+/// assert_eq!(set.len(), 6);
+/// assert!(set.contains(11));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    /// Sorted, non-empty and neither overlapping nor touching.
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// The ranges of the set in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    /// Total number of values in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |len, r| len + (r.end - r.start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// The smallest and largest value bounding the set, as a single range.
+    pub fn span(&self) -> Option<Range<T>> {
+        Some(self.ranges.first()?.start..self.ranges.last()?.end)
+    }
+
+    /// Adds all values of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Removes all values of `range`, splitting the ranges it cuts through.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first >= last {
+            return;
+        }
+
+        let before = self.ranges[first].start..range.start;
+        let after = range.end..self.ranges[last - 1].end;
+        let kept = [before, after].into_iter().filter(|r| !r.is_empty());
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            // the range that ends first can't overlap anything after the other one.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Values of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    /// Splits the set into the values inside `range` and the values outside of it.
+    pub fn split(&self, range: Range<T>) -> (Self, Self) {
+        let mask = Self::from(range);
+        (self.intersection(&mask), self.difference(&mask))
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Endpoint> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Debug> Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+impl<T: Display> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ranges: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|r| format!("{}..{}", r.start, r.end))
+            .collect();
+        write!(f, "{{{}}}", ranges.join(", "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise offset table: values in a source range move to the same position in a destination range,
+/// all other values stay where they are. Source ranges must not overlap.
+///
+/// ```
+/// # use advent_of_code::{interval::{IntervalSet, RangeMap}, parse::uints};
+/// // "destination source length" as in almanac puzzles.
+/// let map: RangeMap<u64> = ["50 98 2", "52 50 48"]
+///     .iter()
+///     .map(|line| uints(line).collect::<Vec<_>>())
+///     .map(|v| (v[1]..v[1] + v[2], v[0]))
+///     .collect();
+/// assert_eq!(map.map_value(79), 81);
+/// assert_eq!(map.map(&IntervalSet::from(79..93)), IntervalSet::from(81..95));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RangeMap<T> {
+    /// Source ranges with the start of their destination, sorted by source.
+    entries: Vec<(Range<T>, T)>,
+}
+
+impl<T: Endpoint> RangeMap<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Moves the values of `source` to the range starting at `destination`.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        let i = self
+            .entries
+            .partition_point(|(s, _)| s.start < source.start);
+        self.entries.insert(i, (source, destination));
+    }
+
+    pub fn map_value(&self, value: T) -> T {
+        self.entries
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    /// Maps every value of the set. Ranges are split where they cross the boundary of a source range.
+    pub fn map(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut unmapped = set.clone();
+        let mut mapped = IntervalSet::new();
+
+        for (source, destination) in &self.entries {
+            let (inside, outside) = unmapped.split(source.clone());
+            for range in inside.iter() {
+                let start = *destination + (range.start - source.start);
+                mapped.insert(start..start + (range.end - range.start));
+            }
+            unmapped = outside;
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T: Endpoint> FromIterator<(Range<T>, T)> for RangeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source, destination);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{IntervalSet, RangeMap};
+    #[allow(unused_imports)]
+    use crate::random::Rng;
+    #[allow(unused_imports)]
+    use std::ops::Range;
+
+    /// Values `0..64` as bits, the model the interval sets are checked against.
+    #[allow(dead_code)]
+    fn bits(range: &Range<u64>) -> u64 {
+        (range.start..range.end).fold(0, |bits, i| bits | 1 << i)
+    }
+
+    #[allow(dead_code)]
+    fn model(set: &IntervalSet<u64>) -> u64 {
+        set.iter().fold(0, |acc, r| acc | bits(&r))
+    }
+
+    #[allow(dead_code)]
+    fn assert_normalized(set: &IntervalSet<u64>) {
+        let ranges = set.ranges();
+        assert!(ranges.iter().all(|r| !r.is_empty()), "{set:?}");
+        assert!(ranges.windows(2).all(|w| w[0].end < w[1].start), "{set:?}");
+    }
+
+    #[test]
+    fn merges_and_splits_ranges() {
+        let mut set = IntervalSet::from(0..10);
+        set.insert(10..12);
+        set.insert(20..25);
+        assert_eq!(set.ranges(), &[0..12, 20..25]);
+        set.remove(3..21);
+        assert_eq!(set.ranges(), &[0..3, 21..25]);
+        set.insert(2..22);
+        assert_eq!(set, IntervalSet::from(0..25));
+        assert_eq!(set.len(), 25);
+        assert_eq!(set.span(), Some(0..25));
+        assert_eq!(set.to_string(), "{0..25}");
+
+        let (inside, outside) = set.split(5..30);
+        assert_eq!(inside, IntervalSet::from(5..25));
+        assert_eq!(outside, IntervalSet::from(0..5));
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let map: RangeMap<i64> = [(0..10, 100), (20..30, -20)].into_iter().collect();
+        assert_eq!(map.map_value(5), 105);
+        assert_eq!(map.map_value(15), 15);
+        assert_eq!(map.map_value(25), -15);
+        let mapped = map.map(&IntervalSet::from(5..25));
+        assert_eq!(mapped.ranges(), &[-20..-15, 10..20, 105..110]);
+    }
+
+    /// Applies random operations and compares every result with the bitset model.
+    #[test]
+    fn matches_bitset_model() {
+        let mut rng = Rng::new(0x2024);
+        let range = |rng: &mut Rng| {
+            let start = rng.below(64);
+            start..(start + rng.below(16)).min(64)
+        };
+
+        for _ in 0..200 {
+            let mut a = IntervalSet::new();
+            let mut b = IntervalSet::new();
+            let (mut model_a, mut model_b) = (0u64, 0u64);
+
+            for _ in 0..12 {
+                let r = range(&mut rng);
+                match rng.below(4) {
+                    0 | 1 => {
+                        model_a |= bits(&r);
+                        a.insert(r);
+                    }
+                    2 => {
+                        model_a &= !bits(&r);
+                        a.remove(r);
+                    }
+                    _ => {
+                        model_b |= bits(&r);
+                        b.insert(r);
+                    }
+                }
+                assert_normalized(&a);
+                assert_eq!(model(&a), model_a);
+                assert_eq!(a.len(), u64::from(model_a.count_ones()));
+            }
+
+            for value in 0..64 {
+                assert_eq!(a.contains(value), model_a & 1 << value != 0);
+            }
+            let unions = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+            for (set, expected) in [
+                (&unions, model_a | model_b),
+                (&intersection, model_a & model_b),
+                (&difference, model_a & !model_b),
+            ] {
+                assert_normalized(set);
+                assert_eq!(model(set), expected);
+            }
+
+            let r = range(&mut rng);
+            let (inside, outside) = a.split(r.clone());
+            assert_eq!(model(&inside), model_a & bits(&r));
+            assert_eq!(model(&outside), model_a & !bits(&r));
+
+            // shifting a random window by a random offset, mapped value by value.
+            let source = range(&mut rng);
+            let destination = rng.below(48);
+            let map: RangeMap<u64> = [(source.clone(), destination)].into_iter().collect();
+            let expected = (0..64u64)
+                .filter(|v| model_a & 1 << v != 0)
+                .fold(0u128, |acc, v| acc | 1 << map.map_value(v));
+            let mapped = map.map(&a);
+            assert_normalized(&mapped);
+            let actual = mapped
+                .iter()
+                .flat_map(|r| r.start..r.end)
+                .fold(0u128, |acc, v| acc | 1 << v);
+            assert_eq!(actual, expected);
+        }
+    }
+}
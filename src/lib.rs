@@ -1,3 +1,23 @@
+pub mod anonymize;
+pub mod bitgrid;
+pub mod check;
+pub mod direction;
+pub mod equation;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod ordering;
+pub mod parse;
+pub mod pattern;
+pub mod point;
+pub mod random;
+pub mod render;
 pub mod template;
+pub mod trace;
+pub mod visualize;
+pub mod walker;
 
 // Use this file to add helper functions and additional modules.
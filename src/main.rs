@@ -1,18 +1,23 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::cli;
+use advent_of_code::template::commands::{
+    all, anonymize, completions, download, generate, leaderboard, read, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, CliError, CommandSpec, GLOBAL_FLAGS};
+    use advent_of_code::template::commands::solve;
+    use advent_of_code::template::{config, Day};
+    use advent_of_code::trace::Level;
+    use std::{env, path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
@@ -24,30 +29,111 @@ mod args {
         },
         Solve {
             day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            options: solve::Options,
         },
         All {
             release: bool,
         },
+        Generate {
+            day: Day,
+            size: Option<usize>,
+            seed: Option<u64>,
+            overwrite: bool,
+        },
+        Anonymize {
+            day: Day,
+            seed: Option<u64>,
+            overwrite: bool,
+            check: bool,
+        },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
         },
+        Leaderboard {
+            day: Option<Day>,
+            id: Option<u64>,
+            cached: bool,
+        },
+        Completions {
+            shell: String,
+        },
+        Help {
+            command: Option<&'static CommandSpec>,
+        },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let name = args.subcommand()?;
+
+        // forward configuration overrides as environment variables, so they apply to child processes as well.
+        for global in GLOBAL_FLAGS {
+            if global.flag.value.is_none() {
+                if args.contains(global.flag.name) {
+                    env::set_var(global.env, "1");
+                }
+            } else if let Some(value) = args.opt_value_from_str::<_, String>(global.flag.name)? {
+                env::set_var(global.env, value);
+            }
+        }
+
+        let Some(name) = name else {
+            if args.contains(["-h", "--help"]) {
+                return Ok(AppArguments::Help { command: None });
+            }
+            eprintln!("No command specified.\n\n{}", cli::help());
+            process::exit(1);
+        };
+
+        let spec = cli::find_command(&name).ok_or(CliError::UnknownCommand { name })?;
+
+        if spec.find_flag("--help").is_some() && args.contains(["-h", "--help"]) {
+            return Ok(AppArguments::Help {
+                command: Some(spec),
+            });
+        }
+
+        // pico-args would parse a misspelled flag in front of a positional as its value, so check flags first.
+        let remaining = args.finish();
+        let remaining_str: Vec<String> = remaining
+            .iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        CliError::check_flags(spec, &remaining_str)?;
+        let mut args = pico_args::Arguments::from_vec(remaining);
+
+        let app_args = parse_command(spec, &mut args)
+            .map_err(|e| format!("{e}\nRun `cargo {} --help` for usage.", spec.name))?;
+
+        let remaining: Vec<String> = args
+            .finish()
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        CliError::check_remaining(spec, &remaining)?;
+
+        // fail early on an invalid configuration instead of inside a child process.
+        config::get();
+
+        Ok(app_args)
+    }
+
+    fn parse_command(
+        spec: &CommandSpec,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let app_args = match spec.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
             },
-            Some("time") => {
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
 
@@ -57,39 +143,108 @@ mod args {
                     store,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
+            "download" => {
+                let force = args.contains("--force");
+
+                AppArguments::Download {
+                    day: args.free_from_str()?,
+                    force,
+                }
+            }
+            "read" => AppArguments::Read {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "scaffold" => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+
+                AppArguments::Scaffold {
+                    day: args.free_from_str()?,
+                    download,
+                    overwrite,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "solve" => {
+                let release = args.contains("--release");
+                let submit_part = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let visualize = args.contains("--visualize");
+                let render = args.contains("--render");
+                // `--trace` takes an optional level, written as `--trace=<level>`.
+                let trace = if args.contains("--trace") {
+                    Some(Level::default())
+                } else {
+                    args.opt_value_from_str("--trace")?
+                };
+                let trace_file: Option<PathBuf> = args.opt_value_from_str("--trace-file")?;
+                // a trace file without a level traces at the default one.
+                let trace = trace.or_else(|| trace_file.is_some().then(Level::default));
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    options: solve::Options {
+                        release,
+                        dhat,
+                        visualize,
+                        render,
+                        trace,
+                        trace_file,
+                        submit_part,
+                    },
+                }
             }
-        };
+            "gen" => {
+                let size = args.opt_value_from_str("--size")?;
+                let seed = args.opt_value_from_str("--seed")?;
+                let overwrite = args.contains("--overwrite");
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+                AppArguments::Generate {
+                    day: args.free_from_str()?,
+                    size,
+                    seed,
+                    overwrite,
+                }
+            }
+            "anonymize" => {
+                let seed = args.opt_value_from_str("--seed")?;
+                let overwrite = args.contains("--overwrite");
+                let check = args.contains("--check");
+
+                AppArguments::Anonymize {
+                    day: args.free_from_str()?,
+                    seed,
+                    overwrite,
+                    check,
+                }
+            }
+            "leaderboard" => {
+                let id = args.opt_value_from_str("--id")?;
+                let cached = args.contains("--cached");
+
+                AppArguments::Leaderboard {
+                    day: args.opt_free_from_str()?,
+                    id,
+                    cached,
+                }
+            }
+            "completions" => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            "help" => {
+                let command = match args.opt_free_from_str::<String>()? {
+                    Some(name) => {
+                        Some(cli::find_command(&name).ok_or(CliError::UnknownCommand { name })?)
+                    }
+                    None => None,
+                };
+                AppArguments::Help { command }
+            }
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            _ => unreachable!("every command in `cli::COMMANDS` is handled"),
+        };
 
         Ok(app_args)
     }
@@ -104,7 +259,25 @@ fn main() {
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
             AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::Download { day, force } => download::handle(day, force),
+            AppArguments::Generate {
+                day,
+                size,
+                seed,
+                overwrite,
+            } => generate::handle(day, size, seed, overwrite),
+            AppArguments::Anonymize {
+                day,
+                seed,
+                overwrite,
+                check,
+            } => anonymize::handle(day, seed, overwrite, check),
+            AppArguments::Leaderboard { day, id, cached } => leaderboard::handle(day, id, cached),
+            AppArguments::Completions { shell } => completions::handle(&shell),
+            AppArguments::Help { command } => match command {
+                Some(command) => println!("{}", cli::command_help(command)),
+                None => println!("{}", cli::help()),
+            },
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
@@ -113,32 +286,12 @@ fn main() {
             } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, false);
                 }
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+            AppArguments::Solve { day, options } => solve::handle(day, &options),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
@@ -0,0 +1,408 @@
+/// Number theory for `i64`, `u64` and `i128`: gcd/lcm, modular arithmetic, the Chinese remainder theorem,
+/// digits, integer square roots and prime factorization.
+/// Methods that can overflow come in a panicking and a `checked_` variant, like the std integer methods.
+use std::fmt::Display;
+
+/// Number theory methods, implemented for `i64`, `u64` and `i128`.
+///
+/// ```
+/// # use advent_of_code::math::Integer;
+/// assert_eq!(12u64.lcm(18), 36);
+/// assert_eq!(3i64.mod_inv(7), Some(5));
+/// assert_eq!(12u64.concat(345), 12345);
+/// ```
+pub trait Integer: Copy + Ord + Display + Sized {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+    /// Returns `None` if the result does not fit, which only happens for `gcd(MIN, 0)`.
+    fn checked_gcd(self, other: Self) -> Option<Self>;
+
+    /// Least common multiple, always non-negative. It is 0 if either argument is 0.
+    fn checked_lcm(self, other: Self) -> Option<Self>;
+
+    /// `self ^ exp mod modulus`, in `0..modulus`. Intermediate products can't overflow.
+    ///
+    /// # Panics
+    /// If `modulus` is not positive.
+    fn mod_pow(self, exp: u64, modulus: Self) -> Self;
+
+    /// The `x` in `0..modulus` with `self * x ≡ 1 (mod modulus)`, if `self` and `modulus` are coprime.
+    /// Returns `None` for a modulus that is not positive.
+    fn mod_inv(self, modulus: Self) -> Option<Self>;
+
+    /// Number of decimal digits, ignoring the sign. 0 has one digit.
+    fn digit_count(self) -> u32;
+
+    /// Appends the digits of `other`, e.g. `12 || 345 = 12345`. A negative `self` keeps its sign.
+    /// Returns `None` on overflow or if `other` is negative.
+    fn checked_concat(self, other: Self) -> Option<Self>;
+
+    /// Floor of the square root, `None` for negative numbers.
+    fn int_sqrt(self) -> Option<Self>;
+
+    /// Prime factors of the absolute value with their multiplicities, in ascending order.
+    /// 0 and 1 have none. Uses trial division, so it is slow for large numbers with two large prime factors.
+    fn factorize(self) -> Vec<(Self, u32)>;
+
+    fn to_i128(self) -> Option<i128>;
+
+    fn from_i128(value: i128) -> Option<Self>;
+
+    fn gcd(self, other: Self) -> Self {
+        self.checked_gcd(other).expect("gcd overflows")
+    }
+
+    fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other).expect("lcm overflows")
+    }
+
+    fn concat(self, other: Self) -> Self {
+        self.checked_concat(other).expect("concatenation overflows")
+    }
+
+    fn is_square(self) -> bool {
+        self.int_sqrt().is_some_and(|root| {
+            matches!((root.to_i128(), self.to_i128()), (Some(r), Some(v)) if r.checked_mul(r) == Some(v))
+        })
+    }
+}
+
+macro_rules! impl_integer {
+    ($t:ty, $magnitude:expr, $negative:expr) => {
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_gcd(self, other: Self) -> Option<Self> {
+                Self::try_from(gcd_u128($magnitude(self), $magnitude(other))).ok()
+            }
+
+            fn checked_lcm(self, other: Self) -> Option<Self> {
+                let (a, b) = ($magnitude(self), $magnitude(other));
+                if a == 0 || b == 0 {
+                    return Some(0);
+                }
+                Self::try_from((a / gcd_u128(a, b)).checked_mul(b)?).ok()
+            }
+
+            fn mod_pow(self, exp: u64, modulus: Self) -> Self {
+                assert!(
+                    modulus != 0 && !$negative(modulus),
+                    "modulus must be positive"
+                );
+                let m = modulus as u128;
+                let mut base = self.rem_euclid(modulus) as u128;
+                let mut exp = exp;
+                let mut result = 1 % m;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        result = mul_mod(result, base, m);
+                    }
+                    base = mul_mod(base, base, m);
+                    exp >>= 1;
+                }
+                result as Self
+            }
+
+            fn mod_inv(self, modulus: Self) -> Option<Self> {
+                if modulus == 0 || $negative(modulus) {
+                    return None;
+                }
+                let m = i128::try_from(modulus).ok()?;
+                let a = i128::try_from(self.rem_euclid(modulus)).ok()?;
+                mod_inv_i128(a, m).map(|x| x as Self)
+            }
+
+            fn digit_count(self) -> u32 {
+                $magnitude(self).checked_ilog10().unwrap_or(0) + 1
+            }
+
+            fn checked_concat(self, other: Self) -> Option<Self> {
+                if $negative(other) {
+                    return None;
+                }
+                let shifted = self.checked_mul((10 as Self).checked_pow(other.digit_count())?)?;
+                if $negative(self) {
+                    shifted.checked_sub(other)
+                } else {
+                    shifted.checked_add(other)
+                }
+            }
+
+            fn int_sqrt(self) -> Option<Self> {
+                u128::try_from(self).ok().map(|v| v.isqrt() as Self)
+            }
+
+            fn factorize(self) -> Vec<(Self, u32)> {
+                factorize_u128($magnitude(self))
+                    .into_iter()
+                    .map(|(p, k)| (p as Self, k))
+                    .collect()
+            }
+
+            fn to_i128(self) -> Option<i128> {
+                i128::try_from(self).ok()
+            }
+
+            fn from_i128(value: i128) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+        }
+    };
+}
+
+impl_integer!(i64, |x: i64| u128::from(x.unsigned_abs()), |x: i64| x < 0);
+impl_integer!(u64, u128::from, |_| false);
+impl_integer!(i128, i128::unsigned_abs, |x: i128| x < 0);
+
+fn gcd_u128(a: u128, b: u128) -> u128 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// `a * b mod m` for `a, b < m`, doubling instead of multiplying when the product could overflow.
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (mut a, mut b, mut result) = (a, b, 0u128);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// `a + b mod m` for `a, b < m`, which can't overflow since `m <= u128::MAX`.
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Extended Euclid for `0 <= a < m`. The Bézout coefficients stay below `m`, so nothing overflows.
+fn mod_inv_i128(a: i128, m: i128) -> Option<i128> {
+    let (mut old_r, mut r) = (a, m);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    (old_r == 1).then(|| old_s.rem_euclid(m))
+}
+
+fn factorize_u128(mut n: u128) -> Vec<(u128, u32)> {
+    let mut factors = Vec::new();
+    let mut divide = |n: &mut u128, p: u128| {
+        let mut k = 0;
+        while n.is_multiple_of(p) {
+            *n /= p;
+            k += 1;
+        }
+        if k > 0 {
+            factors.push((p, k));
+        }
+    };
+
+    if n < 2 {
+        return Vec::new();
+    }
+    divide(&mut n, 2);
+    let mut p = 3;
+    while p * p <= n {
+        divide(&mut n, p);
+        p += 2;
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Solves `x ≡ residue (mod modulus)` for all `(residue, modulus)` pairs.
+/// Returns the smallest non-negative solution and the combined modulus, the lcm of all moduli.
+/// Moduli need not be coprime. Returns `None` if the congruences contradict each other,
+/// a modulus is not positive, or the result does not fit.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// ```
+pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let mut combined: (i128, i128) = (0, 1);
+
+    for (residue, modulus) in congruences {
+        let m2 = modulus.to_i128()?;
+        if m2 <= 0 {
+            return None;
+        }
+        let r2 = residue.to_i128()?.rem_euclid(m2);
+        let (r1, m1) = combined;
+
+        let g = gcd_u128(m1 as u128, m2 as u128) as i128;
+        let diff = r2.checked_sub(r1)?;
+        if diff % g != 0 {
+            return None;
+        }
+
+        // x = r1 + m1 * k with m1 * k ≡ r2 - r1 (mod m2), so k ≡ diff/g * (m1/g)^-1 (mod m2/g).
+        let step = m2 / g;
+        let k = mul_mod(
+            (diff / g).rem_euclid(step) as u128,
+            mod_inv_i128((m1 / g).rem_euclid(step), step)? as u128,
+            step as u128,
+        ) as i128;
+        let lcm = m1.checked_mul(step)?;
+        combined = (r1.checked_add(m1.checked_mul(k)?)?.rem_euclid(lcm), lcm);
+    }
+
+    Some((T::from_i128(combined.0)?, T::from_i128(combined.1)?))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{crt, Integer};
+    #[allow(unused_imports)]
+    use crate::random::Rng;
+
+    #[allow(dead_code)]
+    fn is_prime(n: u64) -> bool {
+        n >= 2
+            && (2..n)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn handles_edge_cases() {
+        assert_eq!(0i64.gcd(0), 0);
+        assert_eq!((-12i64).gcd(18), 6);
+        assert_eq!(i64::MIN.checked_gcd(0), None);
+        assert_eq!(i64::MIN.gcd(2), 2);
+        assert_eq!(0u64.lcm(5), 0);
+        assert_eq!((-4i64).lcm(6), 12);
+        assert_eq!(u64::MAX.checked_lcm(2), None);
+
+        assert_eq!(2i64.mod_pow(10, 1000), 24);
+        assert_eq!((-2i64).mod_pow(3, 5), 2);
+        assert_eq!(5u64.mod_pow(0, 1), 0);
+        assert_eq!(u64::MAX.mod_pow(2, u64::MAX - 1), 1);
+        let big = i128::MAX;
+        assert_eq!((big - 1).mod_pow(2, big), 1);
+        assert_eq!(4i64.mod_inv(8), None);
+        assert_eq!(5i64.mod_inv(1), Some(0));
+        assert_eq!(3i64.mod_inv(-7), None);
+
+        assert_eq!(0u64.digit_count(), 1);
+        assert_eq!((-100i64).digit_count(), 3);
+        assert_eq!(i128::MIN.digit_count(), 39);
+        assert_eq!(1u64.concat(0), 10);
+        assert_eq!((-12i64).concat(34), -1234);
+        assert_eq!(12i64.checked_concat(-3), None);
+        assert_eq!(u64::MAX.checked_concat(1), None);
+
+        assert_eq!(15u64.int_sqrt(), Some(3));
+        assert_eq!((-4i64).int_sqrt(), None);
+        assert!(16i64.is_square());
+        assert!(!15i64.is_square());
+        assert_eq!(360u64.factorize(), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!((-7i64).factorize(), vec![(7, 1)]);
+        assert_eq!(1u64.factorize(), vec![]);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(
+            crt([(0u64, 7), (12, 13), (55, 59)]).map(|(x, _)| x),
+            Some(350)
+        );
+        // moduli sharing factors.
+        assert_eq!(crt([(2i64, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>([]), Some((0, 1)));
+        assert_eq!(crt([(1i64, 0)]), None);
+        assert_eq!(
+            crt([(1u64, u64::MAX), (0, u64::MAX - 1)]).map(|(_, m)| m),
+            None
+        );
+        let (p, q) = ((1i128 << 61) - 1, 1i128 << 62);
+        let (x, m) = crt([(1, p), (0, q)]).unwrap();
+        assert_eq!((x % p, x % q, m), (1, 0, p * q));
+    }
+
+    /// Compares everything with naive implementations on small random inputs.
+    #[test]
+    fn matches_naive_implementations() {
+        let mut rng = Rng::new(0x2024);
+        for _ in 0..1000 {
+            let a = rng.below(2000) as i64 - 1000;
+            let b = rng.below(2000) as i64 - 1000;
+
+            let naive_gcd = (1..=a.abs().max(b.abs()))
+                .rev()
+                .find(|d| a % d == 0 && b % d == 0)
+                .unwrap_or(0);
+            assert_eq!(a.gcd(b), naive_gcd, "gcd({a}, {b})");
+
+            let naive_lcm = if a == 0 || b == 0 {
+                0
+            } else {
+                (1..).map(|k| k * a.abs()).find(|m| m % b == 0).unwrap()
+            };
+            assert_eq!(a.lcm(b), naive_lcm, "lcm({a}, {b})");
+
+            let m = 1 + rng.below(100) as i64;
+            let exp = rng.below(20);
+            let naive_pow = (0..exp).fold(1 % m, |acc, _| (acc * a).rem_euclid(m));
+            assert_eq!(a.mod_pow(exp, m), naive_pow, "{a}^{exp} mod {m}");
+
+            let naive_inv = (0..m).find(|x| (a * x).rem_euclid(m) == 1 % m);
+            assert_eq!(a.mod_inv(m), naive_inv, "{a}^-1 mod {m}");
+
+            assert_eq!(a.digit_count() as usize, a.abs().to_string().len());
+            if b >= 0 {
+                assert_eq!(a.concat(b), format!("{a}{b}").parse::<i64>().unwrap());
+            }
+
+            let n = rng.below(100_000);
+            let naive_sqrt = (0..).find(|r: &u64| (r + 1) * (r + 1) > n).unwrap();
+            assert_eq!(n.int_sqrt(), Some(naive_sqrt));
+
+            let factors = n.factorize();
+            if n > 1 {
+                assert_eq!(factors.iter().map(|(p, k)| p.pow(*k)).product::<u64>(), n);
+            }
+            assert!(factors.iter().all(|(p, _)| is_prime(*p)));
+            assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+
+            let congruences: Vec<(i64, i64)> = (0..1 + rng.below(3))
+                .map(|_| (rng.below(50) as i64 - 25, 1 + rng.below(12) as i64))
+                .collect();
+            let modulus = congruences.iter().fold(1, |acc, (_, m)| acc.lcm(*m));
+            let naive_crt = (0..modulus)
+                .find(|x| congruences.iter().all(|(r, m)| (x - r).rem_euclid(*m) == 0))
+                .map(|x| (x, modulus));
+            assert_eq!(
+                crt(congruences.iter().copied()),
+                naive_crt,
+                "{congruences:?}"
+            );
+        }
+    }
+}
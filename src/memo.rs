@@ -0,0 +1,325 @@
+/// Memoization for recursive solvers and table-based dynamic programming.
+/// Caches are plain values owned by the caller, so `part_one` and `part_two` never share state.
+/// Debug builds count cache hits and misses, see [`CacheStats`].
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// How often a cache was able to answer a lookup. Only counted in debug builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+
+    #[inline]
+    fn record(&mut self, hit: bool) {
+        if cfg!(debug_assertions) {
+            if hit {
+                self.hits += 1;
+            } else {
+                self.misses += 1;
+            }
+        }
+    }
+
+    /// The statistics in debug builds, `None` in release builds where nothing is counted.
+    fn report(self) -> Option<Self> {
+        cfg!(debug_assertions).then_some(self)
+    }
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A cache of computed values keyed by hashable state.
+/// The computation receives the cache, so recursive functions can look up their subproblems.
+///
+/// ```
+/// # use advent_of_code::memo::Memo;
+/// fn arrangements(memo: &mut Memo<usize, u64>, n: usize) -> u64 {
+///     memo.get_or_compute(n, |memo| match n {
+///         0 | 1 => 1,
+///         _ => arrangements(memo, n - 1) + arrangements(memo, n - 2),
+///     })
+/// }
+/// assert_eq!(arrangements(&mut Memo::new(), 80), 37889062373143906);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: CacheStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.record(true);
+            return value.clone();
+        }
+        self.stats.record(false);
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn stats(&self) -> Option<CacheStats> {
+        self.stats.report()
+    }
+}
+
+/// A recursive closure with a cache, created by [`memoize`].
+pub struct Memoized<K, V, F> {
+    memo: Memo<K, V>,
+    f: F,
+}
+
+/// Wraps a recursive function given as a closure. The closure receives a `recurse` function
+/// to call itself through the cache, and the key to compute.
+///
+/// ```
+/// # use advent_of_code::memo::memoize;
+/// let stones = [125u64, 17];
+/// // the number of stones `stone` splits into after `blinks` blinks.
+/// let mut count = memoize(|recurse, (stone, blinks): (u64, u32)| {
+///     let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+///     match (blinks, stone) {
+///         (0, _) => 1,
+///         (_, 0) => recurse((1, blinks - 1)),
+///         _ if digits % 2 == 0 => {
+///             let half = 10u64.pow(digits / 2);
+///             recurse((stone / half, blinks - 1)) + recurse((stone % half, blinks - 1))
+///         }
+///         _ => recurse((stone * 2024, blinks - 1)),
+///     }
+/// });
+/// assert_eq!(stones.iter().map(|&s| count.get((s, 25))).sum::<u64>(), 55312);
+/// ```
+pub fn memoize<K, V, F>(f: F) -> Memoized<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memoized {
+        memo: Memo::new(),
+        f,
+    }
+}
+
+impl<K, V, F> Memoized<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn get(&mut self, key: K) -> V {
+        call(&self.f, &mut self.memo, key)
+    }
+
+    pub fn memo(&self) -> &Memo<K, V> {
+        &self.memo
+    }
+
+    pub fn stats(&self) -> Option<CacheStats> {
+        self.memo.stats()
+    }
+}
+
+fn call<K, V, F>(f: &F, memo: &mut Memo<K, V>, key: K) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    memo.get_or_compute(key.clone(), |memo| f(&mut |k| call(f, memo, k), key))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A memo for keys that map to indices below a known bound, stored in a flat table instead of a hash map.
+///
+/// ```
+/// # use advent_of_code::memo::Table;
+/// // ways to climb `n` steps taking 1, 2 or 3 at a time.
+/// fn ways(table: &mut Table<u64>, n: usize) -> u64 {
+///     table.get_or_compute(n, |table| match n {
+///         0 => 1,
+///         _ => (1..=3.min(n)).map(|step| ways(table, n - step)).sum(),
+///     })
+/// }
+/// assert_eq!(ways(&mut Table::new(31), 30), 53798080);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Table<V> {
+    values: Vec<Option<V>>,
+    stats: CacheStats,
+}
+
+impl<V: Clone> Table<V> {
+    /// A table for the indices `0..size`.
+    pub fn new(size: usize) -> Self {
+        Self {
+            values: vec![None; size],
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Returns the value at `index`, or computes and stores it.
+    ///
+    /// # Panics
+    /// If `index` is outside of the table.
+    pub fn get_or_compute(&mut self, index: usize, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = &self.values[index] {
+            self.stats.record(true);
+            return value.clone();
+        }
+        self.stats.record(false);
+        let value = compute(self);
+        self.values[index] = Some(value.clone());
+        value
+    }
+
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.values.get(index)?.as_ref()
+    }
+
+    pub fn size(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn stats(&self) -> Option<CacheStats> {
+        self.stats.report()
+    }
+}
+
+/// Bottom-up dynamic programming: fills a table of `len` values in order,
+/// computing each one from the values before it.
+///
+/// ```
+/// # use advent_of_code::memo::tabulate;
+/// let fibonacci = tabulate(10, |done: &[u64], i| if i < 2 { i as u64 } else { done[i - 1] + done[i - 2] });
+/// assert_eq!(fibonacci[9], 34);
+/// ```
+pub fn tabulate<V>(len: usize, mut f: impl FnMut(&[V], usize) -> V) -> Vec<V> {
+    let mut table = Vec::with_capacity(len);
+    for i in 0..len {
+        let value = f(&table, i);
+        table.push(value);
+    }
+    table
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{memoize, tabulate, CacheStats, Memo, Table};
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut paths = memoize(|recurse, (x, y): (u32, u32)| -> u64 {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                recurse((x - 1, y)) + recurse((x, y - 1))
+            }
+        });
+        assert_eq!(paths.get((16, 16)), 601080390);
+        // every point of the 17x17 lattice except the unreachable corner is computed exactly once.
+        assert_eq!(paths.memo().len(), 17 * 17 - 1);
+
+        if let Some(stats) = paths.stats() {
+            assert_eq!(stats.misses, 17 * 17 - 1);
+            assert!(stats.hits > 0);
+        }
+    }
+
+    #[test]
+    fn counts_hits_in_debug_builds() {
+        let mut memo: Memo<u8, u8> = Memo::new();
+        memo.get_or_compute(1, |_| 10);
+        assert_eq!(memo.get_or_compute(1, |_| unreachable!()), 10);
+        assert_eq!(memo.get(&1), Some(&10));
+
+        let expected = CacheStats { hits: 1, misses: 1 };
+        assert_eq!(memo.stats(), cfg!(debug_assertions).then_some(expected));
+        assert_eq!(expected.to_string(), "1 hits, 1 misses (50.0% hit rate)");
+    }
+
+    #[test]
+    fn fills_tables() {
+        // longest increasing subsequence ending at each index.
+        let values = [3, 1, 4, 1, 5, 9, 2, 6];
+        let lengths = tabulate(values.len(), |done: &[usize], i| {
+            1 + (0..i)
+                .filter(|&j| values[j] < values[i])
+                .map(|j| done[j])
+                .max()
+                .unwrap_or(0)
+        });
+        assert_eq!(lengths, vec![1, 1, 2, 1, 3, 4, 2, 4]);
+
+        let mut table = Table::new(4);
+        assert_eq!(table.get_or_compute(3, |_| 'x'), 'x');
+        assert_eq!(table.get(3), Some(&'x'));
+        assert_eq!(table.get(2), None);
+        assert_eq!(table.get(9), None);
+    }
+}
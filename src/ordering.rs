@@ -0,0 +1,289 @@
+/// Ordering rules of the form `a|b` ("`a` has to come before `b`"), as used by page ordering puzzles.
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use petgraph::graphmap::DiGraphMap;
+
+use crate::parse::{pair, ParseError};
+
+/// A set of "`a` before `b`" rules with constant-time lookup.
+///
+/// ```
+/// # use advent_of_code::ordering::OrderingRules;
+/// let rules: OrderingRules<u32> = "47|53\n97|47".parse().unwrap();
+/// assert!(rules.is_ordered(&[97, 47, 53]));
+/// assert_eq!(rules.topological_sort(&[53, 47, 97]), Ok(vec![97, 47, 53]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderingRules<T: Eq + Hash> {
+    rules: HashSet<(T, T)>,
+}
+
+impl<T: Eq + Hash> Default for OrderingRules<T> {
+    fn default() -> Self {
+        Self {
+            rules: HashSet::new(),
+        }
+    }
+}
+
+impl<T> OrderingRules<T>
+where
+    T: Copy + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rule that `before` comes before `after`.
+    pub fn insert(&mut self, before: T, after: T) {
+        self.rules.insert((before, after));
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.rules.iter().copied()
+    }
+
+    /// Whether a rule puts `a` before `b`.
+    pub fn precedes(&self, a: T, b: T) -> bool {
+        self.rules.contains(&(a, b))
+    }
+
+    /// Compares two items by the rule between them. Items without a rule compare as equal.
+    pub fn compare(&self, a: T, b: T) -> Ordering {
+        if self.precedes(a, b) {
+            Ordering::Less
+        } else if self.precedes(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Returns the positions of the first pair of items that breaks a rule.
+    pub fn first_violation(&self, sequence: &[T]) -> Option<(usize, usize)> {
+        (0..sequence.len()).find_map(|i| {
+            (i + 1..sequence.len())
+                .find(|&j| self.precedes(sequence[j], sequence[i]))
+                .map(|j| (i, j))
+        })
+    }
+
+    /// Whether no rule puts a later item before an earlier one.
+    pub fn is_ordered(&self, sequence: &[T]) -> bool {
+        self.first_violation(sequence).is_none()
+    }
+
+    /// Sorts the sequence with [`OrderingRules::compare`].
+    /// This is only consistent if the rules order every pair of items in `sequence`, as in puzzles.
+    /// Use [`OrderingRules::topological_sort`] otherwise.
+    pub fn sort(&self, sequence: &mut [T]) {
+        sequence.sort_by(|a, b| self.compare(*a, *b));
+    }
+
+    /// Orders `items` so that every rule between two of them holds.
+    /// Items that are not constrained keep their relative order. Fails with a cycle of items if there is one.
+    pub fn topological_sort(&self, items: &[T]) -> Result<Vec<T>, Cycle<T>> {
+        let position: HashMap<T, usize> = items.iter().enumerate().map(|(i, &t)| (t, i)).collect();
+        let mut successors = vec![Vec::new(); items.len()];
+        let mut incoming = vec![0; items.len()];
+        for &(before, after) in &self.rules {
+            if let (Some(&b), Some(&a)) = (position.get(&before), position.get(&after)) {
+                if a != b {
+                    successors[b].push(a);
+                    incoming[a] += 1;
+                }
+            }
+        }
+
+        // always take the first remaining item without predecessors, which keeps the input order stable.
+        let mut ready: BTreeSet<usize> = (0..items.len()).filter(|&i| incoming[i] == 0).collect();
+        let mut sorted = Vec::with_capacity(items.len());
+        while let Some(next) = ready.pop_first() {
+            sorted.push(items[next]);
+            for &after in &successors[next] {
+                incoming[after] -= 1;
+                if incoming[after] == 0 {
+                    ready.insert(after);
+                }
+            }
+        }
+
+        if sorted.len() < items.len() {
+            let remaining: Vec<T> = (0..items.len())
+                .filter(|&i| incoming[i] > 0)
+                .map(|i| items[i])
+                .collect();
+            return Err(self.find_cycle(&remaining));
+        }
+        Ok(sorted)
+    }
+
+    /// Follows rules among `remaining`, in which every item has a predecessor, until an item repeats.
+    fn find_cycle(&self, remaining: &[T]) -> Cycle<T> {
+        let mut path = vec![remaining[0]];
+        loop {
+            let current = *path.last().expect("path is never empty");
+            let predecessor = remaining
+                .iter()
+                .copied()
+                .find(|&p| p != current && self.precedes(p, current))
+                .expect("every remaining item has a predecessor");
+
+            if let Some(start) = path.iter().position(|&p| p == predecessor) {
+                let mut cycle = path.split_off(start);
+                cycle.reverse();
+                return Cycle(cycle);
+            }
+            path.push(predecessor);
+        }
+    }
+}
+
+impl<T> OrderingRules<T>
+where
+    T: Copy + Ord + Hash,
+{
+    /// Converts the rules to a graph with an edge from `a` to `b` for every rule `a|b`.
+    pub fn to_graph(&self) -> DiGraphMap<T, ()> {
+        DiGraphMap::from_edges(self.rules.iter().copied())
+    }
+
+    pub fn from_graph(graph: &DiGraphMap<T, ()>) -> Self {
+        Self {
+            rules: graph.all_edges().map(|(a, b, _)| (a, b)).collect(),
+        }
+    }
+}
+
+impl<T> FromIterator<(T, T)> for OrderingRules<T>
+where
+    T: Copy + Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        Self {
+            rules: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Parses one `a|b` rule per line.
+impl<T> FromStr for OrderingRules<T>
+where
+    T: Copy + Eq + Hash + FromStr,
+    T::Err: Display,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines().map(|line| pair(line, "|")).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Items whose rules form a cycle, in rule order: each item has to come before the next,
+/// and the last one before the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T: Display> Display for Cycle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rules form a cycle: ")?;
+        for item in &self.0 {
+            write!(f, "{item} -> ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Debug + Display> std::error::Error for Cycle<T> {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Cycle, OrderingRules};
+    #[allow(unused_imports)]
+    use std::cmp::Ordering;
+
+    #[allow(dead_code)]
+    fn rules() -> OrderingRules<u32> {
+        "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn looks_up_precedence() {
+        let rules = rules();
+        assert!(rules.precedes(47, 53));
+        assert!(!rules.precedes(53, 47));
+        assert_eq!(rules.compare(53, 47), Ordering::Greater);
+        assert_eq!(rules.compare(53, 1), Ordering::Equal);
+    }
+
+    #[test]
+    fn validates_sequences() {
+        let rules = rules();
+        assert!(rules.is_ordered(&[75, 47, 61, 53, 29]));
+        assert_eq!(rules.first_violation(&[75, 97, 47, 61, 53]), Some((0, 1)));
+    }
+
+    #[test]
+    fn sorts_sequences() {
+        let rules = rules();
+        let mut sequence = [97, 13, 75, 29, 47];
+        rules.sort(&mut sequence);
+        assert_eq!(sequence, [97, 75, 47, 29, 13]);
+        assert_eq!(
+            rules.topological_sort(&[97, 13, 75, 29, 47]),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+        // unconstrained items keep their order.
+        assert_eq!(
+            rules.topological_sort(&[5, 53, 4, 47]),
+            Ok(vec![5, 4, 47, 53])
+        );
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let rules: OrderingRules<u32> = [(1, 2), (2, 3), (3, 1), (0, 1)].into_iter().collect();
+        let Err(Cycle(cycle)) = rules.topological_sort(&[0, 1, 2, 3]) else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+        for (i, item) in cycle.iter().enumerate() {
+            assert!(rules.precedes(*item, cycle[(i + 1) % cycle.len()]));
+        }
+        assert_eq!(
+            Cycle(vec![1, 2, 3]).to_string(),
+            "rules form a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+
+    #[test]
+    fn converts_to_petgraph() {
+        let rules = rules();
+        let graph = rules.to_graph();
+        assert_eq!(graph.edge_count(), rules.len());
+        assert!(graph.contains_edge(47, 53));
+        assert_eq!(OrderingRules::from_graph(&graph), rules);
+    }
+}
@@ -0,0 +1,271 @@
+/// Helpers for the input formats that most puzzles share.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// An error which can be returned by the parsers in this module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input does not contain the expected separator.
+    MissingSeparator { input: String, separator: String },
+    /// A value could not be converted to the requested type.
+    InvalidValue { value: String, reason: String },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingSeparator { input, separator } => {
+                write!(f, "expected `{separator}` in `{input}`")
+            }
+            ParseError::InvalidValue { value, reason } => {
+                write!(f, "invalid value `{value}`: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a trimmed value, keeping the offending text in the error.
+pub fn value<T>(s: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let s = s.trim();
+    s.parse().map_err(|e: T::Err| ParseError::InvalidValue {
+        value: s.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Splits `s` once at `separator` and parses both trimmed halves, e.g. `3   4` or `47|53`.
+pub fn pair<A, B>(s: &str, separator: &str) -> Result<(A, B), ParseError>
+where
+    A: FromStr,
+    A::Err: Display,
+    B: FromStr,
+    B::Err: Display,
+{
+    let (a, b) = s
+        .split_once(separator)
+        .ok_or_else(|| ParseError::MissingSeparator {
+            input: s.to_string(),
+            separator: separator.to_string(),
+        })?;
+    Ok((value(a)?, value(b)?))
+}
+
+/// Parses a `key: value value ...` line into the key and its whitespace-separated values.
+pub fn key_values<K, V>(line: &str) -> Result<(K, Vec<V>), ParseError>
+where
+    K: FromStr,
+    K::Err: Display,
+    V: FromStr,
+    V::Err: Display,
+{
+    let (key, values) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::MissingSeparator {
+            input: line.to_string(),
+            separator: ":".to_string(),
+        })?;
+    let values = values
+        .split_whitespace()
+        .map(value)
+        .collect::<Result<_, _>>()?;
+    Ok((value(key)?, values))
+}
+
+/// Parses every item of a `separator`-separated list, e.g. `75,47,61`.
+pub fn list<T>(s: &str, separator: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.split(separator).map(value).collect()
+}
+
+/// Splits the input into blocks separated by blank lines.
+/// Handles `\r\n` line endings and runs of more than one blank line.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || loop {
+        if rest.is_empty() {
+            return None;
+        }
+        let (section, tail) = split_section(rest);
+        rest = tail;
+        let section = section.trim_matches(['\r', '\n']);
+        if !section.is_empty() {
+            return Some(section);
+        }
+    })
+}
+
+/// Splits off everything up to the first blank line.
+fn split_section(s: &str) -> (&str, &str) {
+    let mut start = 0;
+    for line in s.split_inclusive('\n') {
+        if start > 0 && line.trim_end_matches(['\r', '\n']).is_empty() {
+            return (&s[..start], &s[start + line.len()..]);
+        }
+        start += line.len();
+    }
+    (s, "")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extracts all signed integers from `s`, ignoring any other characters.
+/// A `-` directly in front of digits makes the number negative.
+/// Stops at the first number that does not fit into an `i64`, rather than returning a wrong value.
+///
+/// ```
+/// # use advent_of_code::parse::ints;
+/// assert_eq!(ints("x=-3, y=14..20").collect::<Vec<_>>(), vec![-3, 14, 20]);
+/// ```
+pub fn ints(s: &str) -> impl Iterator<Item = i64> + '_ {
+    numbers(s.as_bytes(), true).map_while(|(negative, n)| {
+        if negative {
+            0i64.checked_sub_unsigned(n)
+        } else {
+            i64::try_from(n).ok()
+        }
+    })
+}
+
+/// Extracts all unsigned integers from `s`, ignoring any other characters including `-`.
+/// Stops at the first number that does not fit into a `u64`.
+pub fn uints(s: &str) -> impl Iterator<Item = u64> + '_ {
+    numbers(s.as_bytes(), false).map(|(_, n)| n)
+}
+
+/// Byte-level scanner behind [`ints`] and [`uints`]. Returns whether a number is preceded by `-`, and its digits.
+/// Ends at a number that overflows a `u64`.
+fn numbers(bytes: &[u8], signed: bool) -> impl Iterator<Item = (bool, u64)> + '_ {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let negative = signed && i > 0 && bytes[i - 1] == b'-';
+        let (n, len) = parse_uint(bytes.get(i..)?)?;
+        i += len;
+        Some((negative, n))
+    })
+}
+
+/// Parses the leading ASCII digits of `bytes` without UTF-8 validation.
+/// Returns the number and the count of digits consumed, or `None` if there are no digits
+/// or the number does not fit into a `u64`.
+pub fn parse_uint(bytes: &[u8]) -> Option<(u64, usize)> {
+    let len = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    if len == 0 {
+        return None;
+    }
+    let n = bytes[..len].iter().try_fold(0u64, |n, &b| {
+        n.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+    })?;
+    Some((n, len))
+}
+
+/// Iterates the lines of raw bytes like [`str::lines`], without a trailing `\r`.
+pub fn byte_lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    (!bytes.is_empty())
+        .then_some(bytes)
+        .into_iter()
+        .flat_map(|bytes| bytes.split(|b| *b == b'\n'))
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{
+        byte_lines, ints, key_values, list, pair, parse_uint, sections, uints, value, ParseError,
+    };
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(
+            ints("p=0,4 v=-3,-3").collect::<Vec<_>>(),
+            vec![0, 4, -3, -3]
+        );
+        assert_eq!(uints("1-2, 30").collect::<Vec<_>>(), vec![1, 2, 30]);
+        assert_eq!(ints("a-b").count(), 0);
+        assert_eq!(
+            ints("1 -9223372036854775808 9223372036854775808 2").collect::<Vec<_>>(),
+            vec![1, i64::MIN]
+        );
+        assert_eq!(
+            uints("18446744073709551615 18446744073709551616 3").collect::<Vec<_>>(),
+            vec![u64::MAX]
+        );
+    }
+
+    #[test]
+    fn parses_pairs() {
+        assert_eq!(pair::<u32, u32>("3   4", " "), Ok((3, 4)));
+        assert_eq!(pair::<i32, i32>("47|53", "|"), Ok((47, 53)));
+        assert_eq!(
+            pair::<u32, u32>("3 4", "|"),
+            Err(ParseError::MissingSeparator {
+                input: "3 4".into(),
+                separator: "|".into()
+            })
+        );
+        assert!(matches!(
+            pair::<u32, u32>("3|x", "|"),
+            Err(ParseError::InvalidValue { value, .. }) if value == "x"
+        ));
+    }
+
+    #[test]
+    fn parses_key_values() {
+        assert_eq!(
+            key_values::<i64, i64>("190: 10 19"),
+            Ok((190, vec![10, 19]))
+        );
+        assert_eq!(
+            key_values::<String, u8>("a: 1"),
+            Ok(("a".to_string(), vec![1]))
+        );
+        assert!(key_values::<i64, i64>("190 10 19").is_err());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(list::<u32>("75,47,61", ","), Ok(vec![75, 47, 61]));
+        assert_eq!(value::<u8>(" 7 "), Ok(7));
+    }
+
+    #[test]
+    fn splits_sections() {
+        assert_eq!(
+            sections("a\nb\n\nc\n").collect::<Vec<_>>(),
+            vec!["a\nb", "c"]
+        );
+        assert_eq!(
+            sections("a\r\n\r\n\r\nb").collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(sections("").count(), 0);
+    }
+
+    #[test]
+    fn parses_bytes() {
+        assert_eq!(parse_uint(b"123abc"), Some((123, 3)));
+        assert_eq!(parse_uint(b"abc"), None);
+        assert_eq!(parse_uint(b"18446744073709551616"), None);
+        assert_eq!(
+            byte_lines(b"ab\r\ncd\n").collect::<Vec<_>>(),
+            vec![&b"ab"[..], &b"cd"[..]]
+        );
+        assert_eq!(byte_lines(b"a\n\nb").count(), 3);
+    }
+}
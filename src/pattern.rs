@@ -0,0 +1,223 @@
+/// Pattern matching over [`Grid`]s: words read in straight lines and 2D stencils with wildcards.
+/// Matching compares cells in place, nothing is allocated per candidate position.
+use std::str::FromStr;
+
+use crate::direction::Direction;
+use crate::grid::{Coord, Grid, GridError};
+
+/// A word found in a grid, reading from `start` towards `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordMatch {
+    pub start: Coord,
+    pub direction: Direction,
+}
+
+/// Whether `word` can be read from `start` towards `direction`.
+pub fn word_at<T: PartialEq>(
+    grid: &Grid<T>,
+    word: &[T],
+    start: Coord,
+    direction: Direction,
+) -> bool {
+    let Some((first, rest)) = word.split_first() else {
+        return true;
+    };
+    grid.get(start) == Some(first)
+        && grid
+            .ray(start, direction)
+            .take(rest.len())
+            .map(|c| &grid[c])
+            .eq(rest)
+}
+
+/// Every occurrence of `word` reading in one of `directions`, by start cell in row-major order.
+/// Use [`Direction::ALL`] for all eight directions.
+///
+/// ```
+/// # use advent_of_code::{direction::Direction, grid::Grid, pattern::find_word};
+/// let grid: Grid<char> = "XMAS\nMM..\nA.A.\nS..S".parse().unwrap();
+/// let word: Vec<char> = "XMAS".chars().collect();
+/// assert_eq!(find_word(&grid, &word, &Direction::ALL).count(), 3);
+/// assert_eq!(find_word(&grid, &word, &Direction::ORTHOGONAL).count(), 2);
+/// ```
+pub fn find_word<'a, T: PartialEq>(
+    grid: &'a Grid<T>,
+    word: &'a [T],
+    directions: &'a [Direction],
+) -> impl Iterator<Item = WordMatch> + 'a {
+    grid.coords().flat_map(move |start| {
+        directions
+            .iter()
+            .filter(move |&&direction| word_at(grid, word, start, direction))
+            .map(move |&direction| WordMatch { start, direction })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular pattern of cells, where `None` matches anything.
+///
+/// ```
+/// # use advent_of_code::{grid::{Coord, Grid}, pattern::Stencil};
+/// let grid: Grid<char> = "M.S\n.A.\nM.S".parse().unwrap();
+/// let x_mas = Stencil::parse("M.S\n.A.\nM.S", '.').unwrap();
+/// assert_eq!(x_mas.find(&grid).collect::<Vec<_>>(), vec![Coord::new(0, 0)]);
+/// assert_eq!(x_mas.rotations().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stencil<T> {
+    cells: Grid<Option<T>>,
+}
+
+impl<T: PartialEq> Stencil<T> {
+    pub fn new(cells: Grid<Option<T>>) -> Self {
+        Self { cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Whether the stencil matches with its top-left corner on `corner`.
+    pub fn matches_at(&self, grid: &Grid<T>, corner: Coord) -> bool {
+        if corner.x + self.width() > grid.width() || corner.y + self.height() > grid.height() {
+            return false;
+        }
+        self.cells.iter().all(|(offset, expected)| match expected {
+            None => true,
+            Some(expected) => {
+                grid[Coord::new(corner.x + offset.x, corner.y + offset.y)] == *expected
+            }
+        })
+    }
+
+    /// Top-left corners of all matches in row-major order.
+    pub fn find<'a>(&'a self, grid: &'a Grid<T>) -> impl Iterator<Item = Coord> + 'a {
+        let columns = (grid.width() + 1).saturating_sub(self.width());
+        let rows = (grid.height() + 1).saturating_sub(self.height());
+        (0..rows)
+            .flat_map(move |y| (0..columns).map(move |x| Coord::new(x, y)))
+            .filter(move |&corner| self.matches_at(grid, corner))
+    }
+
+    pub fn count(&self, grid: &Grid<T>) -> usize {
+        self.find(grid).count()
+    }
+}
+
+impl<T: PartialEq + Clone> Stencil<T> {
+    /// The distinct rotations of the stencil by quarter turns clockwise, starting with the stencil itself.
+    pub fn rotations(&self) -> Vec<Self> {
+        let mut rotations: Vec<Self> = Vec::with_capacity(4);
+        let mut cells = self.cells.clone();
+        for _ in 0..4 {
+            let rotation = Self::new(cells.clone());
+            if !rotations.contains(&rotation) {
+                rotations.push(rotation);
+            }
+            cells = cells.rotate_cw();
+        }
+        rotations
+    }
+
+    /// Counts the matches of every distinct rotation. A position where two rotations match counts twice.
+    pub fn count_rotated(&self, grid: &Grid<T>) -> usize {
+        self.rotations().iter().map(|r| r.count(grid)).sum()
+    }
+}
+
+impl Stencil<char> {
+    /// Parses a stencil from lines of characters, reading `wildcard` as "any cell".
+    pub fn parse(s: &str, wildcard: char) -> Result<Self, GridError<std::convert::Infallible>> {
+        Grid::parse_with(s, |c| Ok((c != wildcard).then_some(c))).map(Self::new)
+    }
+}
+
+/// Parses a stencil with `.` as wildcard.
+impl FromStr for Stencil<char> {
+    type Err = GridError<std::convert::Infallible>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, '.')
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{find_word, word_at, Stencil, WordMatch};
+    #[allow(unused_imports)]
+    use crate::{
+        direction::Direction,
+        grid::{Coord, Grid},
+    };
+
+    #[allow(dead_code)]
+    fn grid() -> Grid<char> {
+        [
+            "MMMSXXMASM",
+            "MSAMXMSMSA",
+            "AMXSXMAAMM",
+            "MSAMASMSMX",
+            "XMASAMXAMM",
+            "XXAMMXXAMA",
+            "SMSMSASXSS",
+            "SAXAMASAAA",
+            "MAMMMXMMMM",
+            "MXMXAXMASX",
+        ]
+        .join("\n")
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_words() {
+        let grid = grid();
+        let word: Vec<char> = "XMAS".chars().collect();
+        assert_eq!(find_word(&grid, &word, &Direction::ALL).count(), 18);
+        assert_eq!(
+            find_word(&grid, &word, &[Direction::E]).collect::<Vec<_>>(),
+            vec![
+                WordMatch {
+                    start: Coord::new(5, 0),
+                    direction: Direction::E
+                },
+                WordMatch {
+                    start: Coord::new(0, 4),
+                    direction: Direction::E
+                },
+                WordMatch {
+                    start: Coord::new(5, 9),
+                    direction: Direction::E
+                }
+            ]
+        );
+        assert!(word_at(&grid, &word, Coord::new(4, 1), Direction::W));
+        assert!(!word_at(&grid, &word, Coord::new(0, 0), Direction::W));
+        assert!(word_at(&grid, &[], Coord::new(0, 0), Direction::W));
+    }
+
+    #[test]
+    fn matches_stencils() {
+        let grid = grid();
+        let x_mas: Stencil<char> = "M.S\n.A.\nM.S".parse().unwrap();
+        assert_eq!(x_mas.count_rotated(&grid), 9);
+
+        // symmetric stencils have fewer distinct rotations.
+        let plus: Stencil<char> = ".M.\nMAM\n.M.".parse().unwrap();
+        assert_eq!(plus.rotations().len(), 1);
+        let bar = Stencil::parse("XMAS", '?').unwrap();
+        assert_eq!(bar.rotations().len(), 4);
+        assert_eq!(bar.count_rotated(&grid), 5 + 3);
+
+        let too_large: Stencil<char> = ".\n.\n.\n.\n.\n.\n.\n.\n.\n.\n.".parse().unwrap();
+        assert_eq!(too_large.count(&grid), 0);
+    }
+}
@@ -0,0 +1,297 @@
+/// Integer points and vectors in two and three dimensions.
+/// Points are positions, vectors are offsets between them: `Point - Point = Vec`, `Point + Vec = Point`.
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::direction::Direction;
+use crate::grid::Coord;
+use crate::math::Integer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// Implements constructors, arithmetic, distances and display for a point type and its vector type.
+macro_rules! impl_point {
+    ($point:ident, $vec:ident, $($c:ident),+) => {
+        impl $point {
+            pub const ORIGIN: Self = Self { $($c: 0),+ };
+
+            pub const fn new($($c: i64),+) -> Self {
+                Self { $($c),+ }
+            }
+
+            /// Manhattan (taxicab) distance, the sum of the per-axis distances.
+            pub fn manhattan(self, other: Self) -> i64 {
+                (other - self).manhattan()
+            }
+
+            /// Chebyshev (king's move) distance, the largest per-axis distance.
+            pub fn chebyshev(self, other: Self) -> i64 {
+                (other - self).chebyshev()
+            }
+
+            /// Component-wise minimum.
+            pub fn min(self, other: Self) -> Self {
+                Self { $($c: self.$c.min(other.$c)),+ }
+            }
+
+            /// Component-wise maximum.
+            pub fn max(self, other: Self) -> Self {
+                Self { $($c: self.$c.max(other.$c)),+ }
+            }
+
+            /// Smallest and largest corner of the box containing all `points`, or `None` if there are none.
+            pub fn bounding_box(points: impl IntoIterator<Item = Self>) -> Option<(Self, Self)> {
+                points.into_iter().fold(None, |bounds, p| match bounds {
+                    None => Some((p, p)),
+                    Some((min, max)) => Some((min.min(p), max.max(p))),
+                })
+            }
+
+            /// Whether the point lies in the box spanned by `min` and `max`, inclusive.
+            pub fn within(self, min: Self, max: Self) -> bool {
+                $(self.$c >= min.$c && self.$c <= max.$c)&&+
+            }
+        }
+
+        impl $vec {
+            pub const ZERO: Self = Self { $($c: 0),+ };
+
+            pub const fn new($($c: i64),+) -> Self {
+                Self { $($c),+ }
+            }
+
+            pub fn manhattan(self) -> i64 {
+                0 $(+ self.$c.abs())+
+            }
+
+            pub fn chebyshev(self) -> i64 {
+                0 $(.max(self.$c.abs()))+
+            }
+
+            /// Divides all components by their greatest common divisor, keeping the signs.
+            /// The result is the smallest integer step in the same direction. The zero vector stays zero.
+            pub fn reduced(self) -> Self {
+                let divisor = [$(self.$c),+].into_iter().fold(0, Integer::gcd);
+                if divisor == 0 {
+                    return self;
+                }
+                Self { $($c: self.$c / divisor),+ }
+            }
+        }
+
+        impl Add<$vec> for $point {
+            type Output = $point;
+            fn add(self, rhs: $vec) -> $point {
+                $point { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl AddAssign<$vec> for $point {
+            fn add_assign(&mut self, rhs: $vec) {
+                $(self.$c += rhs.$c;)+
+            }
+        }
+
+        impl Sub<$vec> for $point {
+            type Output = $point;
+            fn sub(self, rhs: $vec) -> $point {
+                $point { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl SubAssign<$vec> for $point {
+            fn sub_assign(&mut self, rhs: $vec) {
+                $(self.$c -= rhs.$c;)+
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $vec;
+            fn sub(self, rhs: $point) -> $vec {
+                $vec { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl Add for $vec {
+            type Output = $vec;
+            fn add(self, rhs: $vec) -> $vec {
+                $vec { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl AddAssign for $vec {
+            fn add_assign(&mut self, rhs: $vec) {
+                $(self.$c += rhs.$c;)+
+            }
+        }
+
+        impl Sub for $vec {
+            type Output = $vec;
+            fn sub(self, rhs: $vec) -> $vec {
+                $vec { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl SubAssign for $vec {
+            fn sub_assign(&mut self, rhs: $vec) {
+                $(self.$c -= rhs.$c;)+
+            }
+        }
+
+        impl Mul<i64> for $vec {
+            type Output = $vec;
+            fn mul(self, rhs: i64) -> $vec {
+                $vec { $($c: self.$c * rhs),+ }
+            }
+        }
+
+        impl Mul<$vec> for i64 {
+            type Output = $vec;
+            fn mul(self, rhs: $vec) -> $vec {
+                rhs * self
+            }
+        }
+
+        impl Neg for $vec {
+            type Output = $vec;
+            fn neg(self) -> $vec {
+                $vec { $($c: -self.$c),+ }
+            }
+        }
+
+        impl Display for $point {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let components = [$(self.$c.to_string()),+];
+                write!(f, "({})", components.join(", "))
+            }
+        }
+
+        impl Display for $vec {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let components = [$(self.$c.to_string()),+];
+                write!(f, "<{}>", components.join(", "))
+            }
+        }
+    };
+}
+
+impl_point!(Point2, Vec2, x, y);
+impl_point!(Point3, Vec3, x, y, z);
+
+/* -------------------------------------------------------------------------- */
+
+impl Point2 {
+    /// Converts to a grid coordinate. Returns `None` for negative components.
+    pub fn to_coord(self) -> Option<Coord> {
+        Some(Coord::new(
+            usize::try_from(self.x).ok()?,
+            usize::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl Vec2 {
+    /// Converts to a `(dx, dy)` delta as taken by [`Grid`](crate::grid::Grid) methods.
+    pub fn to_delta(self) -> Option<(isize, isize)> {
+        Some((isize::try_from(self.x).ok()?, isize::try_from(self.y).ok()?))
+    }
+}
+
+impl TryFrom<Coord> for Point2 {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(coord: Coord) -> Result<Self, Self::Error> {
+        Ok(Self::new(i64::try_from(coord.x)?, i64::try_from(coord.y)?))
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(direction: Direction) -> Self {
+        let (dx, dy) = direction.delta();
+        Self::new(dx as i64, dy as i64)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Point2, Point3, Vec2, Vec3};
+    #[allow(unused_imports)]
+    use crate::{direction::Direction, grid::Coord};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - 2 * (b - a), Point2::new(-5, 10));
+        assert_eq!(-Vec3::new(1, -2, 3), Vec3::new(-1, 2, -3));
+        assert_eq!(
+            Point3::ORIGIN + Vec3::new(1, 2, 3) * 2,
+            Point3::new(2, 4, 6)
+        );
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point3::ORIGIN.manhattan(Point3::new(-1, 2, -3)), 6);
+    }
+
+    #[test]
+    fn reduces_vectors() {
+        assert_eq!(Vec2::new(6, -4).reduced(), Vec2::new(3, -2));
+        assert_eq!(Vec2::new(0, -5).reduced(), Vec2::new(0, -1));
+        assert_eq!(Vec2::ZERO.reduced(), Vec2::ZERO);
+        assert_eq!(Vec3::new(2, 4, 8).reduced(), Vec3::new(1, 2, 4));
+    }
+
+    #[test]
+    fn computes_bounding_boxes() {
+        let points = [Point2::new(3, -1), Point2::new(-2, 4), Point2::new(0, 0)];
+        let (min, max) = Point2::bounding_box(points).unwrap();
+        assert_eq!((min, max), (Point2::new(-2, -1), Point2::new(3, 4)));
+        assert!(Point2::new(3, 4).within(min, max));
+        assert!(!Point2::new(4, 0).within(min, max));
+        assert_eq!(Point2::bounding_box([]), None);
+    }
+
+    #[test]
+    fn converts_to_grid_coords() {
+        assert_eq!(Point2::new(2, 3).to_coord(), Some(Coord::new(2, 3)));
+        assert_eq!(Point2::new(-1, 3).to_coord(), None);
+        assert_eq!(Point2::try_from(Coord::new(2, 3)), Ok(Point2::new(2, 3)));
+        assert_eq!(Vec2::from(Direction::NW), Vec2::new(-1, -1));
+        assert_eq!(Vec2::new(-1, 2).to_delta(), Some((-1, 2)));
+    }
+}
@@ -0,0 +1,510 @@
+/// Image export of grids as PPM, SVG, PNG and animated GIF files, for states too large for the terminal.
+///
+/// PNG and GIF encoding is compiled in with the `render` feature. Solutions write images with [`save`],
+/// which only does something when the solution runs with `--render`; `cargo solve <day> --render` takes care
+/// of both. Files are written to `data/visualizations/<day>/`:
+///
+/// ```
+/// # use advent_of_code::{grid::{Coord, Grid}, render::{self, Palette, Picture, Rgb}, template::Day};
+/// let grid: Grid<char> = "..#\n...".parse().unwrap();
+/// let palette = Palette::new(Rgb::BLACK).with('#', Rgb::GRAY);
+/// let picture = Picture::new(&grid, &palette).paint([Coord::new(0, 1)], Rgb::YELLOW);
+/// render::save(Day::new(6).unwrap(), "start.png", &picture);
+/// ```
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Write as _};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::grid::{Coord, Grid};
+use crate::template::{config, Day};
+
+/// Whether image encoding is compiled in and images were requested with `--render`.
+pub fn enabled() -> bool {
+    static REQUESTED: OnceLock<bool> = OnceLock::new();
+    cfg!(feature = "render")
+        && *REQUESTED.get_or_init(|| std::env::args().any(|arg| arg == "--render"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0x10, 0x10, 0x1a);
+    pub const WHITE: Rgb = Rgb(0xf0, 0xf0, 0xf0);
+    pub const GRAY: Rgb = Rgb(0x70, 0x70, 0x78);
+    pub const RED: Rgb = Rgb(0xe0, 0x40, 0x40);
+    pub const GREEN: Rgb = Rgb(0x40, 0xc0, 0x60);
+    pub const YELLOW: Rgb = Rgb(0xff, 0xd0, 0x30);
+    pub const BLUE: Rgb = Rgb(0x40, 0x70, 0xe0);
+    pub const MAGENTA: Rgb = Rgb(0xd0, 0x50, 0xd0);
+    pub const CYAN: Rgb = Rgb(0x40, 0xc8, 0xd8);
+
+    /// The color as `#rrggbb`.
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colors for each kind of cell, with a fallback for kinds without a color of their own.
+#[derive(Debug, Clone)]
+pub struct Palette<K> {
+    colors: HashMap<K, Rgb>,
+    fallback: Rgb,
+}
+
+impl<K: Eq + Hash> Palette<K> {
+    pub fn new(fallback: Rgb) -> Self {
+        Self {
+            colors: HashMap::new(),
+            fallback,
+        }
+    }
+
+    pub fn with(mut self, kind: K, color: Rgb) -> Self {
+        self.colors.insert(kind, color);
+        self
+    }
+
+    pub fn color(&self, kind: &K) -> Rgb {
+        self.colors.get(kind).copied().unwrap_or(self.fallback)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Svg,
+    Png,
+    Gif,
+}
+
+impl Format {
+    /// The format matching the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Format::Ppm),
+            "svg" => Some(Format::Svg),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Format::Ppm => "PPM",
+            Format::Svg => "SVG",
+            Format::Png => "PNG",
+            Format::Gif => "GIF",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    /// The file extension does not name a known format.
+    UnknownFormat(PathBuf),
+    /// The image cannot be written in this format, or the `render` feature is disabled.
+    Unsupported(Format),
+    /// The image is too large or has too many colors for the format.
+    TooLarge(Format),
+    Encoding(String),
+    Io(io::Error),
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::UnknownFormat(path) => {
+                write!(f, "unknown image format for {}", path.display())
+            }
+            RenderError::Unsupported(format) if cfg!(feature = "render") => {
+                write!(f, "cannot write this image as {format}")
+            }
+            RenderError::Unsupported(format) => {
+                write!(f, "writing {format} requires the `render` feature")
+            }
+            RenderError::TooLarge(format) => write!(f, "image is too large for {format}"),
+            RenderError::Encoding(message) => write!(f, "could not encode image: {message}"),
+            RenderError::Io(e) => write!(f, "could not write image: {e}"),
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+impl From<io::Error> for RenderError {
+    fn from(e: io::Error) -> Self {
+        RenderError::Io(e)
+    }
+}
+
+/// Something that can be written as an image file.
+pub trait Encode {
+    fn encode(&self, format: Format) -> Result<Vec<u8>, RenderError>;
+
+    /// Writes the image in the format matching the extension of `path`.
+    fn write_to(&self, path: &Path) -> Result<(), RenderError> {
+        let format =
+            Format::from_path(path).ok_or_else(|| RenderError::UnknownFormat(path.to_owned()))?;
+        fs::write(path, self.encode(format)?)?;
+        Ok(())
+    }
+}
+
+/// The directory the images of `day` are written to.
+pub fn output_dir(day: Day) -> PathBuf {
+    config::get().data_path("visualizations", &day.to_string())
+}
+
+/// Writes `image` as `name` into the output directory of `day` if rendering is [`enabled`].
+/// Errors are printed instead of returned, so they never fail a solution.
+pub fn save(day: Day, name: &str, image: &impl Encode) {
+    if !enabled() {
+        return;
+    }
+    let dir = output_dir(day);
+    let path = dir.join(name);
+    match fs::create_dir_all(&dir)
+        .map_err(RenderError::from)
+        .and_then(|_| image.write_to(&path))
+    {
+        Ok(()) => eprintln!("Saved {}", path.display()),
+        Err(e) => eprintln!("{e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A grid drawn as squares of `scale` pixels, one color per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    cells: Grid<Rgb>,
+    scale: usize,
+}
+
+impl Picture {
+    /// Colors every cell by its kind.
+    pub fn new<K: Eq + Hash>(grid: &Grid<K>, palette: &Palette<K>) -> Self {
+        Self::with_colors(grid, |kind| palette.color(kind))
+    }
+
+    /// Colors every cell with the color returned by `color`.
+    pub fn with_colors<T>(grid: &Grid<T>, color: impl FnMut(&T) -> Rgb) -> Self {
+        Self {
+            cells: grid.map(color),
+            scale: 4,
+        }
+    }
+
+    /// Sets the size of a cell in pixels. Defaults to 4.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Colors the given cells. Cells outside of the grid are ignored.
+    pub fn paint(mut self, cells: impl IntoIterator<Item = Coord>, color: Rgb) -> Self {
+        for cell in cells {
+            if let Some(c) = self.cells.get_mut(cell) {
+                *c = color;
+            }
+        }
+        self
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> usize {
+        self.cells.width() * self.scale
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> usize {
+        self.cells.height() * self.scale
+    }
+
+    /// The pixels in row-major order.
+    pub fn pixels(&self) -> impl Iterator<Item = Rgb> + '_ {
+        self.cells.rows().flat_map(move |row| {
+            (0..self.scale).flat_map(move |_| {
+                row.iter()
+                    .flat_map(move |&color| std::iter::repeat_n(color, self.scale))
+            })
+        })
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels().flat_map(|Rgb(r, g, b)| [r, g, b]).collect()
+    }
+
+    fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width(), self.height()).into_bytes();
+        out.extend(self.rgb_bytes());
+        out
+    }
+
+    /// One rectangle per horizontal run of equally colored cells, in cell units scaled by the viewport.
+    fn to_svg(&self) -> String {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n",
+            self.width(),
+            self.height(),
+        );
+        for (y, row) in self.cells.rows().enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                let _ = writeln!(
+                    out,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{}\" height=\"1\" fill=\"{}\"/>",
+                    run.len(),
+                    run[0].hex()
+                );
+                x += run.len();
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    #[cfg(feature = "render")]
+    fn to_png(&self) -> Result<Vec<u8>, RenderError> {
+        let too_large = || RenderError::TooLarge(Format::Png);
+        let width = u32::try_from(self.width()).map_err(|_| too_large())?;
+        let height = u32::try_from(self.height()).map_err(|_| too_large())?;
+
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let encoding = |e: png::EncodingError| RenderError::Encoding(e.to_string());
+        let mut writer = encoder.write_header().map_err(encoding)?;
+        writer
+            .write_image_data(&self.rgb_bytes())
+            .map_err(encoding)?;
+        writer.finish().map_err(encoding)?;
+        Ok(out)
+    }
+
+    #[cfg(not(feature = "render"))]
+    fn to_png(&self) -> Result<Vec<u8>, RenderError> {
+        Err(RenderError::Unsupported(Format::Png))
+    }
+}
+
+impl Encode for Picture {
+    fn encode(&self, format: Format) -> Result<Vec<u8>, RenderError> {
+        match format {
+            Format::Ppm => Ok(self.to_ppm()),
+            Format::Svg => Ok(self.to_svg().into_bytes()),
+            Format::Png => self.to_png(),
+            Format::Gif => Recording::new(Duration::ZERO)
+                .frame(self.clone())
+                .encode(format),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Pictures shown one after another, written as a looping animated GIF.
+/// Frames can differ in colors but must share their size.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    delay: Duration,
+    frames: Vec<Picture>,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(100))
+    }
+}
+
+impl Recording {
+    /// A recording that shows each frame for `delay`, rounded to hundredths of a second.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    pub fn push(&mut self, frame: Picture) {
+        self.frames.push(frame);
+    }
+
+    pub fn frame(mut self, frame: Picture) -> Self {
+        self.push(frame);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    #[cfg(feature = "render")]
+    fn to_gif(&self) -> Result<Vec<u8>, RenderError> {
+        let too_large = || RenderError::TooLarge(Format::Gif);
+        let Some(first) = self.frames.first() else {
+            return Err(RenderError::Encoding("recording has no frames".to_string()));
+        };
+        if self
+            .frames
+            .iter()
+            .any(|f| f.width() != first.width() || f.height() != first.height())
+        {
+            return Err(RenderError::Encoding("frames differ in size".to_string()));
+        }
+        let width = u16::try_from(first.width()).map_err(|_| too_large())?;
+        let height = u16::try_from(first.height()).map_err(|_| too_large())?;
+
+        // grids use few colors, so a shared palette avoids quantizing every frame.
+        let mut colors: Vec<Rgb> = self
+            .frames
+            .iter()
+            .flat_map(|f| f.cells.cells().iter().copied())
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+        if colors.len() > 256 {
+            return Err(too_large());
+        }
+        let palette: Vec<u8> = colors.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect();
+        let delay = u16::try_from(self.delay.as_millis() / 10).unwrap_or(u16::MAX);
+
+        let encoding = |e: gif::EncodingError| RenderError::Encoding(e.to_string());
+        let mut out = Vec::new();
+        {
+            let mut encoder =
+                gif::Encoder::new(&mut out, width, height, &palette).map_err(encoding)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(encoding)?;
+            for picture in &self.frames {
+                let indices: Vec<u8> = picture
+                    .pixels()
+                    .map(|color| colors.binary_search(&color).unwrap() as u8)
+                    .collect();
+                let mut frame = gif::Frame::from_indexed_pixels(width, height, indices, None);
+                frame.delay = delay;
+                encoder.write_frame(&frame).map_err(encoding)?;
+            }
+        }
+        Ok(out)
+    }
+
+    #[cfg(not(feature = "render"))]
+    fn to_gif(&self) -> Result<Vec<u8>, RenderError> {
+        Err(RenderError::Unsupported(Format::Gif))
+    }
+}
+
+impl Encode for Recording {
+    fn encode(&self, format: Format) -> Result<Vec<u8>, RenderError> {
+        match format {
+            Format::Gif => self.to_gif(),
+            _ => Err(RenderError::Unsupported(format)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Encode, Format, Palette, Picture, Recording, RenderError, Rgb};
+    #[allow(unused_imports)]
+    use crate::grid::{Coord, Grid};
+    #[allow(unused_imports)]
+    use std::path::Path;
+
+    #[allow(dead_code)]
+    fn picture() -> Picture {
+        let grid: Grid<char> = "..#\n#..".parse().unwrap();
+        let palette = Palette::new(Rgb::BLACK).with('#', Rgb::WHITE);
+        Picture::new(&grid, &palette).paint([Coord::new(2, 1), Coord::new(5, 5)], Rgb::RED)
+    }
+
+    #[test]
+    fn writes_scaled_pixels() {
+        let picture = picture().scale(2);
+        assert_eq!((picture.width(), picture.height()), (6, 4));
+
+        let ppm = picture.encode(Format::Ppm).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+
+        let first_rows: Vec<Rgb> = picture.pixels().take(12).collect();
+        let row = [
+            Rgb::BLACK,
+            Rgb::BLACK,
+            Rgb::BLACK,
+            Rgb::BLACK,
+            Rgb::WHITE,
+            Rgb::WHITE,
+        ];
+        assert_eq!(first_rows, [row, row].concat());
+    }
+
+    #[test]
+    fn merges_svg_runs() {
+        let svg = String::from_utf8(picture().encode(Format::Svg).unwrap()).unwrap();
+        assert!(svg.contains("width=\"12\" height=\"8\" viewBox=\"0 0 3 2\""));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#10101a\"/>"));
+    }
+
+    #[test]
+    fn picks_formats_by_extension() {
+        assert_eq!(
+            Format::from_path(Path::new("a/path.PNG")),
+            Some(Format::Png)
+        );
+        assert_eq!(Format::from_path(Path::new("path.txt")), None);
+        assert!(matches!(
+            Recording::default().encode(Format::Svg),
+            Err(RenderError::Unsupported(Format::Svg))
+        ));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn encodes_png_and_gif() {
+        let png = picture().encode(Format::Png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let recording = Recording::default()
+            .frame(picture())
+            .frame(picture().paint([Coord::new(0, 0)], Rgb::CYAN));
+        let gif = recording.encode(Format::Gif).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+
+        let mismatched = recording.frame(picture().scale(1));
+        assert!(matches!(
+            mismatched.encode(Format::Gif),
+            Err(RenderError::Encoding(_))
+        ));
+    }
+}
@@ -1,16 +1,20 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::input_cache::{self, CacheError, InputStatus};
+use crate::template::{config, Day};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    Offline,
+    InvalidInput(CacheError),
 }
 
 impl Display for AocCommandError {
@@ -21,6 +25,10 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::Offline => {
+                write!(f, "network access is disabled by offline mode.")
+            }
+            AocCommandError::InvalidInput(e) => write!(f, "downloaded input is invalid: {e}"),
         }
     }
 }
@@ -49,27 +57,56 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+/// Downloads input and puzzle description of `day`.
+/// A valid cached input is kept unless `force` is set; only the puzzle description is refreshed then.
+/// Fresh inputs are validated before they replace the cached one.
+pub fn download(day: Day, force: bool) -> Result<(), AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
+    if !force && input_cache::status(day) == InputStatus::Valid {
+        let args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--puzzle-only".into(),
+                "--puzzle-file".into(),
+                puzzle_path.to_string(),
+            ],
+            day,
+        );
+
+        call_aoc_cli(&args)?;
+        println!("---");
+        println!("🎄 Using cached input \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        return Ok(());
+    }
+
+    // download to a temporary file first, so a failed download does not clobber a valid input.
+    let download_path = format!("{input_path}.download");
+
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
             "--input-file".into(),
-            input_path.to_string(),
+            download_path.to_string(),
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
+    call_aoc_cli(&args)?;
+    let raw = fs::read_to_string(&download_path).unwrap_or_default();
+    let _ = fs::remove_file(&download_path);
+    input_cache::store(day, &raw).map_err(AocCommandError::InvalidInput)?;
+
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    Ok(())
 }
 
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
@@ -80,35 +117,40 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+pub fn get_input_path(day: Day) -> String {
+    let path = config::get().data_path("inputs", &format!("{day}.txt"));
+    path.to_string_lossy().into_owned()
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+pub fn get_puzzle_path(day: Day) -> String {
+    let path = config::get().data_path("puzzles", &format!("{day}.md"));
+    path.to_string_lossy().into_owned()
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
+    let config = config::get();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config.year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
 
+    if let Some(session_file) = &config.session_file {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.to_string_lossy().into_owned());
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    if config::get().offline {
+        return Err(AocCommandError::Offline);
+    }
+
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
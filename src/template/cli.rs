@@ -0,0 +1,667 @@
+/// Static description of the command-line interface.
+/// Used to render help text, validate flags and generate shell completions.
+use std::error::Error;
+use std::fmt::{Display, Write};
+
+use crate::template::{ansi, config, ANSI_BOLD, ANSI_RESET};
+
+/// Kind of value a positional argument or a flag accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Any day between 1 and 25.
+    Day,
+    /// A day that already has a solution module in `src/bin`.
+    ScaffoldedDay,
+    /// A solution part, i.e. `1` or `2`.
+    Part,
+    /// A supported shell for completion scripts.
+    Shell,
+    /// The name of another command.
+    Command,
+    /// A puzzle year.
+    Year,
+    /// A numeric id, e.g. of a private leaderboard.
+    Id,
+    /// A file system path.
+    Path,
+    /// An output format of [`config::OutputFormat`].
+    OutputFormat,
+    /// Any non-negative number, e.g. a size or a seed.
+    Number,
+}
+
+#[derive(Debug)]
+pub struct FlagSpec {
+    pub name: &'static str,
+    pub value: Option<ValueKind>,
+    pub help: &'static str,
+}
+
+#[derive(Debug)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ValueKind,
+    pub required: bool,
+}
+
+#[derive(Debug)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub args: &'static [ArgSpec],
+    pub flags: &'static [FlagSpec],
+}
+
+/// A flag accepted by every command that overrides a value of the project configuration.
+#[derive(Debug)]
+pub struct GlobalFlag {
+    pub flag: FlagSpec,
+    /// Environment variable the value is forwarded as.
+    pub env: &'static str,
+}
+
+pub const GLOBAL_FLAGS: &[GlobalFlag] = &[
+    GlobalFlag {
+        flag: FlagSpec {
+            name: "--config",
+            value: Some(ValueKind::Path),
+            help: "Read configuration from this file instead of `aoc.toml`.",
+        },
+        env: config::ENV_CONFIG,
+    },
+    GlobalFlag {
+        flag: FlagSpec {
+            name: "--year",
+            value: Some(ValueKind::Year),
+            help: "Override the puzzle year.",
+        },
+        env: config::ENV_YEAR,
+    },
+    GlobalFlag {
+        flag: FlagSpec {
+            name: "--data-dir",
+            value: Some(ValueKind::Path),
+            help: "Override the directory holding inputs, examples and puzzles.",
+        },
+        env: config::ENV_DATA_DIR,
+    },
+    GlobalFlag {
+        flag: FlagSpec {
+            name: "--bench-budget",
+            value: Some(ValueKind::Number),
+            help: "Override the time budget of a benchmark, in milliseconds.",
+        },
+        env: config::ENV_BENCH_BUDGET_MS,
+    },
+    GlobalFlag {
+        flag: FlagSpec {
+            name: "--output",
+            value: Some(ValueKind::OutputFormat),
+            help: "Override the output format (`pretty` or `plain`).",
+        },
+        env: config::ENV_OUTPUT,
+    },
+    GlobalFlag {
+        flag: FlagSpec {
+            name: "--offline",
+            value: None,
+            help: "Never access the network; use cached inputs and puzzles only.",
+        },
+        env: config::ENV_OFFLINE,
+    },
+];
+
+const HELP_FLAG: FlagSpec = FlagSpec {
+    name: "--help",
+    value: None,
+    help: "Print help for this command.",
+};
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "scaffold",
+        about: "Create the solution module, input and example files for a day.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::Day,
+            required: true,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--download",
+                value: None,
+                help: "Download input and puzzle description after scaffolding.",
+            },
+            FlagSpec {
+                name: "--overwrite",
+                value: None,
+                help: "Overwrite an existing solution module.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "download",
+        about: "Download input and puzzle description for a day via aoc-cli.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::Day,
+            required: true,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--force",
+                value: None,
+                help: "Download the input even if a valid cached copy exists.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "read",
+        about: "Read the puzzle description for a day in the terminal.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::Day,
+            required: true,
+        }],
+        flags: &[HELP_FLAG],
+    },
+    CommandSpec {
+        name: "solve",
+        about: "Run the solution for a day against its puzzle input.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::ScaffoldedDay,
+            required: true,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--release",
+                value: None,
+                help: "Run an optimized build.",
+            },
+            FlagSpec {
+                name: "--dhat",
+                value: None,
+                help: "Profile heap allocations with DHAT.",
+            },
+            FlagSpec {
+                name: "--submit",
+                value: Some(ValueKind::Part),
+                help: "Submit the result of the given part.",
+            },
+            FlagSpec {
+                name: "--visualize",
+                value: None,
+                help: "Show the solution's terminal visualizations.",
+            },
+            FlagSpec {
+                name: "--render",
+                value: None,
+                help: "Save the solution's images to data/visualizations/<day>/.",
+            },
+            FlagSpec {
+                name: "--trace",
+                value: None,
+                help: "Write trace events to stderr. Use `--trace=<level>` to change the level.",
+            },
+            FlagSpec {
+                name: "--trace-file",
+                value: Some(ValueKind::Path),
+                help: "Write trace events to this file instead of stderr.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "all",
+        about: "Run the solutions for all scaffolded days.",
+        args: &[],
+        flags: &[
+            FlagSpec {
+                name: "--release",
+                value: None,
+                help: "Run optimized builds.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "time",
+        about: "Benchmark solutions and optionally store timings in the readme.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::ScaffoldedDay,
+            required: false,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--all",
+                value: None,
+                help: "Bench all days, including days that are already stored.",
+            },
+            FlagSpec {
+                name: "--store",
+                value: None,
+                help: "Write timings to the readme.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "gen",
+        about: "Generate a synthetic puzzle input for a day, e.g. for stress tests.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::Day,
+            required: true,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--size",
+                value: Some(ValueKind::Number),
+                help: "Size of the input, e.g. lines or grid side. Defaults to the size of real inputs.",
+            },
+            FlagSpec {
+                name: "--seed",
+                value: Some(ValueKind::Number),
+                help: "Seed of the generator. Defaults to a random seed.",
+            },
+            FlagSpec {
+                name: "--overwrite",
+                value: None,
+                help: "Overwrite an existing input file.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "anonymize",
+        about: "Anonymize the input of a day and record its answers as a shareable fixture.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::ScaffoldedDay,
+            required: true,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--seed",
+                value: Some(ValueKind::Number),
+                help: "Seed of the transforms. Defaults to a random seed.",
+            },
+            FlagSpec {
+                name: "--overwrite",
+                value: None,
+                help: "Overwrite an existing fixture.",
+            },
+            FlagSpec {
+                name: "--check",
+                value: None,
+                help: "Solve the existing fixture and compare with its recorded answers.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    #[cfg(feature = "today")]
+    CommandSpec {
+        name: "today",
+        about: "Scaffold, download and read the current day of advent.",
+        args: &[],
+        flags: &[
+            FlagSpec {
+                name: "--wait",
+                value: None,
+                help: "Wait for the next puzzle to unlock, then fetch it and extract its examples.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "leaderboard",
+        about: "Show rankings and star times of a private leaderboard.",
+        args: &[ArgSpec {
+            name: "day",
+            kind: ValueKind::Day,
+            required: false,
+        }],
+        flags: &[
+            FlagSpec {
+                name: "--id",
+                value: Some(ValueKind::Id),
+                help: "Id of the private leaderboard. Defaults to `leaderboard.id` of the config.",
+            },
+            FlagSpec {
+                name: "--cached",
+                value: None,
+                help: "Show the cached leaderboard without fetching it.",
+            },
+            HELP_FLAG,
+        ],
+    },
+    CommandSpec {
+        name: "completions",
+        about: "Print a shell completion script to stdout.",
+        args: &[ArgSpec {
+            name: "shell",
+            kind: ValueKind::Shell,
+            required: true,
+        }],
+        flags: &[HELP_FLAG],
+    },
+    CommandSpec {
+        name: "help",
+        about: "Print help for the tool or for a single command.",
+        args: &[ArgSpec {
+            name: "command",
+            kind: ValueKind::Command,
+            required: false,
+        }],
+        flags: &[],
+    },
+];
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// Looks up the spec of a command by name.
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+impl CommandSpec {
+    pub fn find_flag(&self, name: &str) -> Option<&FlagSpec> {
+        self.flags.iter().find(|f| f.name == name)
+    }
+
+    /// Iterates the command's own flags followed by the global flags.
+    pub fn all_flags(&self) -> impl Iterator<Item = &FlagSpec> {
+        self.flags
+            .iter()
+            .chain(GLOBAL_FLAGS.iter().map(|g| &g.flag))
+    }
+
+    fn usage(&self) -> String {
+        let mut usage = format!("cargo {}", self.name);
+        for arg in self.args {
+            if arg.required {
+                let _ = write!(usage, " <{}>", arg.name);
+            } else {
+                let _ = write!(usage, " [{}]", arg.name);
+            }
+        }
+        for flag in self.flags.iter().filter(|f| f.name != HELP_FLAG.name) {
+            match flag.value {
+                Some(kind) => {
+                    let _ = write!(usage, " [{} <{}>]", flag.name, kind.placeholder());
+                }
+                None => {
+                    let _ = write!(usage, " [{}]", flag.name);
+                }
+            }
+        }
+        usage
+    }
+}
+
+impl ValueKind {
+    fn placeholder(self) -> &'static str {
+        match self {
+            ValueKind::Day | ValueKind::ScaffoldedDay => "day",
+            ValueKind::Part => "part",
+            ValueKind::Shell => "shell",
+            ValueKind::Command => "command",
+            ValueKind::Year => "year",
+            ValueKind::Id => "id",
+            ValueKind::Path => "path",
+            ValueKind::OutputFormat => "format",
+            ValueKind::Number => "n",
+        }
+    }
+}
+
+/// Renders the top-level help text listing all commands.
+pub fn help() -> String {
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    let mut out = format!("{bold}Usage:{reset} cargo <command> [options]\n\n");
+    out.push_str(&format!("{bold}Commands:{reset}\n"));
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for command in COMMANDS {
+        let _ = writeln!(out, "  {:width$}  {}", command.name, command.about);
+    }
+    out.push_str(&format!("\n{bold}Global options:{reset}\n"));
+    out.push_str(&render_flags(GLOBAL_FLAGS.iter().map(|g| &g.flag)));
+    out.push_str("\nRun `cargo <command> --help` for details on a command.");
+    out
+}
+
+/// Renders the help text for a single command.
+pub fn command_help(command: &CommandSpec) -> String {
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    let mut out = format!("{}\n\n", command.about);
+    let _ = writeln!(out, "{bold}Usage:{reset} {}", command.usage());
+
+    if !command.flags.is_empty() {
+        out.push_str(&format!("\n{bold}Options:{reset}\n"));
+        out.push_str(&render_flags(command.flags.iter()));
+    }
+
+    out.push_str(&format!("\n{bold}Global options:{reset}\n"));
+    out.push_str(&render_flags(GLOBAL_FLAGS.iter().map(|g| &g.flag)));
+
+    out.trim_end().to_string()
+}
+
+fn render_flags<'a>(flags: impl Iterator<Item = &'a FlagSpec>) -> String {
+    let flags: Vec<_> = flags
+        .map(|f| match f.value {
+            Some(kind) => (format!("{} <{}>", f.name, kind.placeholder()), f.help),
+            None => (f.name.to_string(), f.help),
+        })
+        .collect();
+    let width = flags
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    for (label, help) in flags {
+        let _ = writeln!(out, "  {label:width$}  {help}");
+    }
+    out
+}
+
+/// An error raised when validating command-line arguments against [`COMMANDS`].
+#[derive(Debug)]
+pub enum CliError {
+    UnknownCommand {
+        name: String,
+    },
+    UnknownFlag {
+        command: &'static str,
+        flag: String,
+    },
+    UnexpectedArgument {
+        command: &'static str,
+        value: String,
+    },
+}
+
+impl CliError {
+    /// Checks the arguments of a command for flags it does not accept, before positionals are read.
+    /// Otherwise a misspelled flag in front of a positional would be parsed as its value.
+    pub fn check_flags(command: &'static CommandSpec, args: &[String]) -> Result<(), Self> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with('-') {
+                continue;
+            }
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (arg.as_str(), false),
+            };
+            match command.all_flags().find(|f| f.name == name) {
+                // skip the value, so it is never mistaken for a flag.
+                Some(flag) if flag.value.is_some() && !inline_value => {
+                    args.next();
+                }
+                Some(_) => {}
+                None if name == "-h" => {}
+                None => {
+                    return Err(CliError::UnknownFlag {
+                        command: command.name,
+                        flag: arg.clone(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks arguments left over after parsing a command and reports the first one as an error.
+    pub fn check_remaining(
+        command: &'static CommandSpec,
+        remaining: &[String],
+    ) -> Result<(), Self> {
+        match remaining.first() {
+            None => Ok(()),
+            Some(arg) if arg.starts_with('-') => Err(CliError::UnknownFlag {
+                command: command.name,
+                flag: arg.clone(),
+            }),
+            Some(arg) => Err(CliError::UnexpectedArgument {
+                command: command.name,
+                value: arg.clone(),
+            }),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::UnknownCommand { name } => {
+                write!(f, "unknown command `{name}`.")?;
+                if let Some(candidate) = suggest(name, COMMANDS.iter().map(|c| c.name)) {
+                    write!(f, " Did you mean `{candidate}`?")?;
+                }
+                Ok(())
+            }
+            CliError::UnknownFlag { command, flag } => {
+                write!(f, "unknown flag `{flag}` for `{command}`.")?;
+                let name = flag.split('=').next().unwrap_or(flag);
+                let candidate = find_command(command)
+                    .and_then(|c| suggest(name, c.all_flags().map(|f| f.name)));
+                if let Some(candidate) = candidate {
+                    write!(f, " Did you mean `{candidate}`?")?;
+                }
+                Ok(())
+            }
+            CliError::UnexpectedArgument { command, value } => {
+                write!(f, "unexpected argument `{value}` for `{command}`.")
+            }
+        }
+    }
+}
+
+/// Returns the candidate closest to `input`, if it is similar enough to be a plausible typo.
+pub fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let threshold = (input.len() / 3).max(2);
+    candidates
+        .into_iter()
+        .map(|c| (levenshtein(input, c), c))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j + 1])
+            };
+            prev = current;
+        }
+    }
+
+    row[b.len()]
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{command_help, find_command, levenshtein, suggest, CliError, COMMANDS};
+
+    #[test]
+    fn computes_edit_distance() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("--relase", "--release"), 1);
+        assert_eq!(levenshtein("solve", "slove"), 2);
+        assert_eq!(levenshtein("abc", ""), 3);
+    }
+
+    #[test]
+    fn suggests_nearest_flag() {
+        let flags = ["--release", "--dhat", "--submit"];
+        assert_eq!(suggest("--relase", flags), Some("--release"));
+        assert_eq!(suggest("--sumbit", flags), Some("--submit"));
+        assert_eq!(suggest("--verbose", flags), None);
+    }
+
+    #[test]
+    fn suggests_nearest_command() {
+        let names = COMMANDS.iter().map(|c| c.name);
+        assert_eq!(suggest("scafold", names), Some("scaffold"));
+    }
+
+    #[test]
+    fn renders_command_help() {
+        let help = command_help(find_command("solve").unwrap());
+        assert!(help.contains("cargo solve <day> [--release] [--dhat] [--submit <part>]"));
+        assert!(help.contains("--submit <part>"));
+    }
+
+    #[test]
+    fn reports_unknown_flag_with_suggestion() {
+        let solve = find_command("solve").unwrap();
+        let err = CliError::check_remaining(solve, &["--relase".into()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown flag `--relase` for `solve`. Did you mean `--release`?"
+        );
+        assert!(CliError::check_remaining(solve, &[]).is_ok());
+    }
+
+    #[test]
+    fn reports_unknown_flag_before_positional() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        let solve = find_command("solve").unwrap();
+        let err = CliError::check_flags(solve, &args(&["--relase", "1"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown flag `--relase` for `solve`. Did you mean `--release`?"
+        );
+        let time = find_command("time").unwrap();
+        let err = CliError::check_flags(time, &args(&["--stroe"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown flag `--stroe` for `time`. Did you mean `--store`?"
+        );
+
+        let valid = args(&["--trace=info", "--submit", "1", "--trace-file", "-", "6"]);
+        assert!(CliError::check_flags(solve, &valid).is_ok());
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::anonymize;
+use crate::random::Rng;
+use crate::template::{config, Day};
+
+/// Directory below the data directory that holds anonymized inputs and their answers.
+/// It has the layout of the data directory, so `--data-dir data/fixtures` solves the fixtures.
+const FIXTURES: &str = "fixtures";
+
+pub fn handle(day: Day, seed: Option<u64>, overwrite: bool, check: bool) {
+    let fixtures = config::get().data_dir.join(FIXTURES);
+    let input_path = fixtures.join("inputs").join(format!("{day}.txt"));
+    let answers_path = fixtures.join("answers").join(format!("{day}.txt"));
+
+    if check {
+        check_answers(day, &fixtures, &answers_path);
+        return;
+    }
+
+    let Some(spec) = anonymize::find(day) else {
+        eprintln!("No anonymization spec for day {day}: its input cannot be changed without changing the puzzle.");
+        process::exit(1);
+    };
+
+    let Ok(input) = fs::read_to_string(config::get().data_path("inputs", &format!("{day}.txt")))
+    else {
+        eprintln!("No input for day {day}. Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    if !overwrite && (input_path.exists() || answers_path.exists()) {
+        eprintln!(
+            "Fixture \"{}\" already exists. Pass --overwrite to replace it.",
+            input_path.display()
+        );
+        process::exit(1);
+    }
+
+    // without a seed, every run transforms the input differently. The seed is printed to reproduce it.
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    let anonymized = spec.apply(&input, &mut Rng::new(seed));
+    write(&input_path, &anonymized);
+    println!(
+        "Anonymized input \"{}\" (seed {seed})",
+        input_path.display()
+    );
+
+    let answers = solve(day, &fixtures);
+    write(&answers_path, &answers);
+    println!("Recorded answers \"{}\":", answers_path.display());
+    print!("{answers}");
+}
+
+/// Solves the fixture again and compares its answers to the recorded ones.
+fn check_answers(day: Day, fixtures: &Path, answers_path: &Path) {
+    let Ok(expected) = fs::read_to_string(answers_path) else {
+        eprintln!(
+            "No recorded answers \"{}\". Run `cargo anonymize {day}` first.",
+            answers_path.display()
+        );
+        process::exit(1);
+    };
+
+    let actual = solve(day, fixtures);
+    if actual == expected {
+        println!("Answers of day {day} match the fixture.");
+    } else {
+        eprintln!("Answers of day {day} do not match the fixture.");
+        eprintln!("Expected:\n{expected}Actual:\n{actual}");
+        process::exit(1);
+    }
+}
+
+fn write(path: &Path, contents: &str) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Failed to write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+}
+
+/// Runs the solution of `day` on the input in `data_dir` and returns its answers.
+fn solve(day: Day, data_dir: &Path) -> String {
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--release", "--bin", &day_padded];
+
+    if config::get().offline {
+        args.push("--offline");
+    }
+
+    let output = Command::new("cargo")
+        .args(&args)
+        .env(config::ENV_DATA_DIR, data_dir)
+        .env(config::ENV_OUTPUT, "plain")
+        .stderr(Stdio::inherit())
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => answers(&String::from_utf8_lossy(&output.stdout)),
+        Ok(output) => {
+            eprintln!("Solution for day {day} failed with {}.", output.status);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to run the solution for day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Extracts the answers from the plain output of a solution, without timings.
+fn answers(output: &str) -> String {
+    let mut answers = String::new();
+    let mut in_multiline_answer = false;
+
+    for line in output.lines() {
+        // intermediate results are overwritten with a carriage return.
+        let line = line.rsplit('\r').next().unwrap_or(line);
+        match line.split_once(": ") {
+            Some((part, result)) if part.starts_with("Part ") => {
+                in_multiline_answer = result.starts_with('▼');
+                let result = if in_multiline_answer || result.starts_with('✖') {
+                    &result[..result.chars().next().map_or(0, char::len_utf8)]
+                } else {
+                    result
+                        .rsplit_once(" (")
+                        .map_or(result, |(result, _)| result)
+                };
+                answers.push_str(&format!("{part}: {result}\n"));
+            }
+            _ if in_multiline_answer => {
+                answers.push_str(line);
+                answers.push('\n');
+            }
+            _ => {}
+        }
+    }
+    answers
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::answers;
+
+    #[test]
+    fn strips_timings_from_answers() {
+        let output = "Part 1: 41\rPart 1: 41 (6.6µs)\nSaved data/visualizations/06/path.png\nPart 2: (1, 2)\rPart 2: (1, 2) (1.2ms @ 20 samples)\n";
+        assert_eq!(answers(output), "Part 1: 41\nPart 2: (1, 2)\n");
+    }
+
+    #[test]
+    fn keeps_multiline_and_missing_answers() {
+        let output =
+            "Part 1: ▼ \rPart 1: ▼  (2.0ms)\n#.#\n.#.\nPart 2: ✖\rPart 2: ✖             \n";
+        assert_eq!(answers(output), "Part 1: ▼\n#.#\n.#.\nPart 2: ✖\n");
+    }
+}
@@ -0,0 +1,357 @@
+use std::fmt::Write;
+use std::process;
+
+use crate::template::cli::{suggest, CommandSpec, ValueKind, COMMANDS, SHELLS};
+
+pub fn handle(shell: &str) {
+    let script = match shell {
+        "bash" => bash(),
+        "zsh" => zsh(),
+        "fish" => fish(),
+        _ => {
+            eprintln!(
+                "Unsupported shell: {shell}. Expected one of: {}.",
+                SHELLS.join(", ")
+            );
+            if let Some(candidate) = suggest(shell, SHELLS.iter().copied()) {
+                eprintln!("Did you mean `{candidate}`?");
+            }
+            process::exit(1);
+        }
+    };
+
+    print!("{script}");
+}
+
+/// Shell expression that lists the candidate words for a value.
+/// Days are resolved when completing, so newly scaffolded days show up without regenerating the script.
+/// Paths are completed by each shell's own file completion and yield no words.
+fn words_for(kind: ValueKind, fn_prefix: &str) -> String {
+    match kind {
+        ValueKind::Day => format!("$({fn_prefix}_days)"),
+        ValueKind::ScaffoldedDay => format!("$({fn_prefix}_scaffolded_days)"),
+        ValueKind::Part => "1 2".into(),
+        ValueKind::Shell => SHELLS.join(" "),
+        ValueKind::Command => command_names(),
+        ValueKind::OutputFormat => "pretty plain".into(),
+        ValueKind::Year | ValueKind::Id | ValueKind::Number | ValueKind::Path => String::new(),
+    }
+}
+
+fn command_names() -> String {
+    COMMANDS
+        .iter()
+        .map(|c| c.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn flag_names(command: &CommandSpec) -> String {
+    command
+        .all_flags()
+        .map(|f| f.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn positional_words(command: &CommandSpec, fn_prefix: &str) -> String {
+    command
+        .args
+        .iter()
+        .map(|a| words_for(a.kind, fn_prefix))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn bash() -> String {
+    let mut out = String::from(
+        r#"# bash completion for the advent-of-code cargo aliases.
+# usage: source <(cargo completions bash)
+
+__aoc_days() { seq -w 1 25; }
+__aoc_scaffolded_days() { command ls src/bin 2>/dev/null | sed -n 's/^\([0-9][0-9]\)\.rs$/\1/p'; }
+
+__aoc_cargo() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    COMPREPLY=()
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        if declare -F _cargo >/dev/null; then _cargo "$@"; fi
+"#,
+    );
+    let _ = writeln!(
+        out,
+        "        COMPREPLY+=($(compgen -W \"{}\" -- \"$cur\"))",
+        command_names()
+    );
+    out.push_str(
+        r#"        return
+    fi
+
+    case "${COMP_WORDS[1]}" in
+"#,
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(out, "        {})", command.name);
+        out.push_str("            case \"$prev\" in\n");
+        for flag in command.all_flags() {
+            match flag.value {
+                Some(ValueKind::Path) => {
+                    let _ = writeln!(
+                        out,
+                        "                {}) COMPREPLY=($(compgen -f -- \"$cur\")); return ;;",
+                        flag.name
+                    );
+                }
+                Some(kind) => {
+                    let _ = writeln!(
+                        out,
+                        "                {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;",
+                        flag.name,
+                        words_for(kind, "__aoc")
+                    );
+                }
+                None => {}
+            }
+        }
+        out.push_str("            esac\n");
+        let words = [flag_names(command), positional_words(command, "__aoc")];
+        let _ = writeln!(
+            out,
+            "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            words.join(" ").trim()
+        );
+        out.push_str("            ;;\n");
+    }
+
+    out.push_str(
+        r#"        *)
+            if declare -F _cargo >/dev/null; then _cargo "$@"; fi
+            ;;
+    esac
+}
+
+complete -F __aoc_cargo cargo
+"#,
+    );
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''").replace(':', "\\:")
+}
+
+fn zsh() -> String {
+    let mut out = String::from(
+        r"#compdef cargo
+# zsh completion for the advent-of-code cargo aliases.
+# usage: source <(cargo completions zsh)
+
+__aoc_days() { seq -w 1 25; }
+__aoc_scaffolded_days() { command ls src/bin 2>/dev/null | sed -n 's/^\([0-9][0-9]\)\.rs$/\1/p'; }
+
+__aoc_cargo() {
+    if (( CURRENT == 2 )); then
+        local -a commands
+        commands=(
+",
+    );
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "            '{}:{}'",
+            command.name,
+            zsh_escape(command.about)
+        );
+    }
+    out.push_str(
+        r"        )
+        (( $+functions[_cargo] )) && _cargo
+        _describe 'command' commands
+        return
+    fi
+
+    case $words[2] in
+",
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(out, "        {})", command.name);
+        out.push_str("            case $words[CURRENT-1] in\n");
+        for flag in command.all_flags() {
+            match flag.value {
+                Some(ValueKind::Path) => {
+                    let _ = writeln!(out, "                {}) _files; return ;;", flag.name);
+                }
+                Some(kind) => {
+                    let _ = writeln!(
+                        out,
+                        "                {}) compadd -- {}; return ;;",
+                        flag.name,
+                        words_for(kind, "__aoc")
+                    );
+                }
+                None => {}
+            }
+        }
+        out.push_str("            esac\n");
+        out.push_str("            local -a flags\n            flags=(\n");
+        for flag in command.all_flags() {
+            let _ = writeln!(
+                out,
+                "                '{}:{}'",
+                flag.name,
+                zsh_escape(flag.help)
+            );
+        }
+        out.push_str("            )\n            _describe 'option' flags\n");
+        if !command.args.is_empty() {
+            let _ = writeln!(
+                out,
+                "            compadd -- {}",
+                positional_words(command, "__aoc")
+            );
+        }
+        out.push_str("            ;;\n");
+    }
+
+    out.push_str(
+        r"        *)
+            (( $+functions[_cargo] )) && _cargo
+            ;;
+    esac
+}
+
+compdef __aoc_cargo cargo
+",
+    );
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn fish_escape(s: &str) -> String {
+    s.replace('\'', "\\'")
+}
+
+fn fish_words(kind: ValueKind) -> String {
+    match kind {
+        ValueKind::Day => "(__aoc_days)".into(),
+        ValueKind::ScaffoldedDay => "(__aoc_scaffolded_days)".into(),
+        other => words_for(other, "__aoc"),
+    }
+}
+
+fn fish() -> String {
+    let mut out = String::from(
+        r"# fish completion for the advent-of-code cargo aliases.
+# usage: cargo completions fish | source
+
+function __aoc_days
+    seq -w 1 25
+end
+
+function __aoc_scaffolded_days
+    command ls src/bin 2>/dev/null | string replace -rf '^(\d\d)\.rs$' '$1'
+end
+
+",
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "complete -c cargo -n __fish_use_subcommand -f -a {} -d '{}'",
+            command.name,
+            fish_escape(command.about)
+        );
+    }
+    out.push('\n');
+
+    for command in COMMANDS {
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+        for flag in command.all_flags() {
+            let long = flag.name.trim_start_matches("--");
+            match flag.value {
+                Some(ValueKind::Path) => {
+                    let _ = writeln!(
+                        out,
+                        "complete -c cargo -n '{condition}' -l {long} -r -F -d '{}'",
+                        fish_escape(flag.help)
+                    );
+                }
+                Some(kind) => {
+                    let _ = writeln!(
+                        out,
+                        "complete -c cargo -n '{condition}' -l {long} -x -a '{}' -d '{}'",
+                        fish_words(kind),
+                        fish_escape(flag.help)
+                    );
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "complete -c cargo -n '{condition}' -l {long} -d '{}'",
+                        fish_escape(flag.help)
+                    );
+                }
+            }
+        }
+        for arg in command.args {
+            let _ = writeln!(
+                out,
+                "complete -c cargo -n '{condition}' -f -a '{}'",
+                fish_words(arg.kind)
+            );
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{bash, fish, zsh};
+
+    #[test]
+    fn bash_completes_commands_and_flags() {
+        let script = bash();
+        assert!(script.contains("complete -F __aoc_cargo cargo"));
+        assert!(
+            script.contains("--submit) COMPREPLY=($(compgen -W \"1 2\" -- \"$cur\")); return ;;")
+        );
+        assert!(script.contains(
+            "--release --dhat --submit --visualize --render --trace --trace-file --help --config --year --data-dir --bench-budget --output --offline $(__aoc_scaffolded_days)"
+        ));
+        assert!(script.contains("--config) COMPREPLY=($(compgen -f -- \"$cur\")); return ;;"));
+    }
+
+    #[test]
+    fn zsh_describes_commands() {
+        let script = zsh();
+        assert!(script.starts_with("#compdef cargo"));
+        assert!(script
+            .contains("'scaffold:Create the solution module, input and example files for a day.'"));
+        assert!(script.contains("compadd -- $(__aoc_days)"));
+    }
+
+    #[test]
+    fn fish_completes_flag_values() {
+        let script = fish();
+        assert!(script.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from solve' -l submit -x -a '1 2'"
+        ));
+        assert!(script.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from solve' -f -a '(__aoc_scaffolded_days)'"
+        ));
+    }
+}
@@ -1,13 +1,30 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::input_cache::{self, InputStatus};
+use crate::template::{aoc_cli, config, Day};
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, force: bool) {
+    if config::get().offline {
+        match input_cache::status(day) {
+            InputStatus::Valid => {
+                println!("🎄 Offline mode: using cached input for day {day}.");
+                return;
+            }
+            InputStatus::Missing => {
+                eprintln!("Offline mode: no cached input for day {day}.");
+            }
+            InputStatus::Invalid(e) => {
+                eprintln!("Offline mode: cached input for day {day} is invalid: {e}");
+            }
+        }
+        process::exit(1);
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = aoc_cli::download(day, force) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
@@ -0,0 +1,69 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    process,
+};
+
+use crate::generate;
+use crate::random::Rng;
+use crate::template::{config, Day};
+
+/// Directory below the data directory that holds generated inputs, apart from the downloaded ones.
+/// It has the layout of the data directory, so `--data-dir data/generated` solves the generated inputs.
+const GENERATED: &str = "generated";
+
+pub fn handle(day: Day, size: Option<usize>, seed: Option<u64>, overwrite: bool) {
+    let Some(generator) = generate::find(day) else {
+        eprintln!("No input generator for day {day}.");
+        process::exit(1);
+    };
+
+    let size = size.unwrap_or(generator.default_size);
+    // without a seed, every run generates a different input. The seed is printed to reproduce it.
+    let seed = seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    let input = generator.generate(&mut Rng::new(seed), size);
+
+    let path = config::get()
+        .data_dir
+        .join(GENERATED)
+        .join("inputs")
+        .join(format!("{day}.txt"));
+    let mut options = OpenOptions::new();
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+
+    let result = options
+        .write(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(input.as_bytes()));
+
+    match result {
+        Ok(()) => println!(
+            "Generated input \"{}\" ({size} {}, seed {seed})",
+            path.display(),
+            generator.unit
+        ),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            eprintln!(
+                "Input file \"{}\" already exists. Pass --overwrite to replace it.",
+                path.display()
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to write input file: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    fs, process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::leaderboard::{
+    FileSource, HttpSource, Leaderboard, LeaderboardError, LeaderboardSource,
+};
+use crate::template::unlock::latest_event_year;
+use crate::template::{config, Day};
+
+/// The website asks to not fetch private leaderboards more often than every 15 minutes.
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+pub fn handle(day: Option<Day>, id: Option<u64>, cached: bool) {
+    let config = config::get();
+
+    let Some(id) = id.or(config.leaderboard_id) else {
+        eprintln!("No leaderboard id. Pass `--id <id>` or set `id` in the `[leaderboard]` section of the config.");
+        process::exit(1);
+    };
+
+    let year = config.year.unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        latest_event_year(now)
+    });
+
+    let cache = FileSource {
+        path: config.data_path("leaderboards", &format!("{year}-{id}.json")),
+    };
+
+    let is_fresh = fs::metadata(&cache.path)
+        .and_then(|m| m.modified())
+        .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age < CACHE_TTL));
+
+    let result = if cached || config.offline || is_fresh {
+        Leaderboard::load(&cache)
+    } else {
+        fetch(&HttpSource::from_config(year, id), &cache).or_else(|e| stale(&cache, e))
+    };
+
+    match result {
+        Ok(leaderboard) => match day {
+            Some(day) => println!("{}", leaderboard.render_day(day)),
+            None => println!("{}", leaderboard.render_rankings()),
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            if cached || config.offline {
+                eprintln!(
+                    "No usable cached leaderboard at \"{}\".",
+                    cache.path.display()
+                );
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// Falls back to a cached leaderboard of any age when fetching failed, and reports the fetch error otherwise.
+fn stale(cache: &FileSource, error: LeaderboardError) -> Result<Leaderboard, LeaderboardError> {
+    let Ok(leaderboard) = Leaderboard::load(cache) else {
+        return Err(error);
+    };
+
+    let age = fs::metadata(&cache.path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .map_or_else(String::new, |age| {
+            format!(" from {} minutes ago", age.as_secs() / 60)
+        });
+    eprintln!("{error}");
+    eprintln!(
+        "Showing the cached leaderboard{age} at \"{}\", it may be out of date.",
+        cache.path.display()
+    );
+    Ok(leaderboard)
+}
+
+/// Fetches the leaderboard and stores the response in the cache once it parsed.
+fn fetch(
+    source: &impl LeaderboardSource,
+    cache: &FileSource,
+) -> Result<Leaderboard, LeaderboardError> {
+    let json = source.fetch()?;
+    let leaderboard = Leaderboard::from_json(&json)?;
+
+    if let Some(dir) = cache.path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&cache.path, json)?;
+
+    Ok(leaderboard)
+}
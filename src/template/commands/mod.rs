@@ -1,6 +1,12 @@
 pub mod all;
+pub mod anonymize;
+pub mod completions;
 pub mod download;
+pub mod generate;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
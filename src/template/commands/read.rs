@@ -1,8 +1,20 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, config, Day};
 
 pub fn handle(day: Day) {
+    if config::get().offline {
+        let puzzle_path = aoc_cli::get_puzzle_path(day);
+        match fs::read_to_string(&puzzle_path) {
+            Ok(puzzle) => println!("{puzzle}"),
+            Err(_) => {
+                eprintln!("Offline mode: no cached puzzle at \"{puzzle_path}\".");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
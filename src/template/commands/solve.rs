@@ -1,28 +1,70 @@
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{config, Day};
+use crate::trace::Level;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// Flags of `cargo solve` that change how the solution is built and run.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub dhat: bool,
+    pub visualize: bool,
+    pub render: bool,
+    pub trace: Option<Level>,
+    pub trace_file: Option<PathBuf>,
+    pub submit_part: Option<u8>,
+}
+
+pub fn handle(day: Day, options: &Options) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    let mut features = vec![];
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
+    if options.dhat {
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
+    } else if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    if options.visualize {
+        features.push("visualize");
+    }
+
+    if options.render {
+        features.push("render");
+    }
+
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
+    if config::get().offline {
+        cmd_args.push("--offline".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if options.visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if options.render {
+        cmd_args.push("--render".to_string());
+    }
+
+    if let Some(level) = options.trace {
+        cmd_args.push(format!("--trace={level}"));
+        if let Some(file) = &options.trace_file {
+            cmd_args.push(format!("--trace-file={}", file.display()));
+        }
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
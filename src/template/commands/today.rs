@@ -0,0 +1,85 @@
+use std::{
+    io::{stdout, Write},
+    process, thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::unlock::{current_or_next_unlock, format_countdown};
+use crate::template::{config, examples, Day};
+
+/// Upper bound of the random delay added after unlock, so that not everyone hits the server at the same second.
+const MAX_JITTER_MILLIS: u64 = 5000;
+
+pub fn handle(wait: bool) {
+    let day = if wait {
+        wait_for_unlock()
+    } else {
+        match Day::today() {
+            Some(day) => day,
+            None => {
+                eprintln!(
+                    "`today` command can only be run between the 1st and \
+                    the 25th of december. Please use `scaffold` with a specific day \
+                    or `today --wait` to wait for the next puzzle."
+                );
+                process::exit(1)
+            }
+        }
+    };
+
+    scaffold::handle(day, false);
+    download::handle(day, false);
+
+    match examples::write_from_puzzle(day) {
+        Ok(paths) => {
+            for path in paths {
+                println!("🎄 Extracted example to \"{path}\".");
+            }
+        }
+        Err(e) => eprintln!("Failed to extract examples: {e}"),
+    }
+
+    read::handle(day);
+}
+
+/// Shows a countdown until the next puzzle unlocks and returns its day once it is available.
+/// During advent, today's puzzle is returned right away once it has unlocked.
+fn wait_for_unlock() -> Day {
+    let (year, day, unlock) = current_or_next_unlock(Utc::now());
+
+    if let Some(configured) = config::get().year.filter(|y| i32::from(*y) != year) {
+        eprintln!(
+            "The next puzzle unlocks in {year}, but the configured year is {configured}. \
+            Update `year` in aoc.toml or pass `--year {year}`."
+        );
+        process::exit(1);
+    }
+
+    if unlock <= Utc::now() {
+        println!("🔓 Day {day} of {year} is unlocked.");
+        return day;
+    }
+
+    let jitter = Duration::from_millis(
+        u64::from(Utc::now().timestamp_subsec_millis()) * MAX_JITTER_MILLIS / 1000,
+    );
+    let target = unlock + jitter;
+
+    println!(
+        "🎄 Day {day} of {year} unlocks at {}.",
+        unlock.with_timezone(&chrono::Local)
+    );
+
+    let mut stdout = stdout();
+    while let Ok(remaining) = (target - Utc::now()).to_std() {
+        print!("\r⏳ {} ", format_countdown(remaining));
+        let _ = stdout.flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    println!("\r🔓 Unlocked!          ");
+
+    day
+}
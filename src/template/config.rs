@@ -0,0 +1,425 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Values are resolved in the following order, later sources taking precedence:
+///  1. built-in defaults.
+///  2. the config file (`aoc.toml`, or the path in `AOC_CONFIG`).
+///  3. environment variables (`AOC_YEAR`, `AOC_DATA_DIR`, ...).
+///  4. command-line flags, which the CLI forwards as environment variables so child processes see them too.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use toml::{Table, Value};
+
+pub const DEFAULT_CONFIG_PATH: &str = "aoc.toml";
+
+pub const ENV_CONFIG: &str = "AOC_CONFIG";
+pub const ENV_YEAR: &str = "AOC_YEAR";
+pub const ENV_DATA_DIR: &str = "AOC_DATA_DIR";
+pub const ENV_SESSION_FILE: &str = "AOC_SESSION_FILE";
+pub const ENV_BENCH_BUDGET_MS: &str = "AOC_BENCH_BUDGET_MS";
+pub const ENV_OUTPUT: &str = "AOC_OUTPUT";
+pub const ENV_OFFLINE: &str = "AOC_OFFLINE";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Puzzle year passed to aoc-cli. Defaults to the latest event when unset.
+    pub year: Option<u16>,
+    /// Directory holding `inputs`, `examples`, `puzzles` and `timings.json`.
+    pub data_dir: PathBuf,
+    /// Location of the session cookie. Defaults to aoc-cli's own lookup when unset.
+    pub session_file: Option<PathBuf>,
+    /// Guarantees that no command accesses the network.
+    pub offline: bool,
+    pub bench: BenchConfig,
+    pub output: OutputFormat,
+    pub readme: ReadmeConfig,
+    pub templates: TemplatesConfig,
+    /// Id of the private leaderboard shown by `cargo leaderboard`.
+    pub leaderboard_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchConfig {
+    /// Approximate total time spent benching a single part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output with ANSI styling.
+    Pretty,
+    /// Human-readable output without ANSI escape codes.
+    Plain,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadmeConfig {
+    pub path: PathBuf,
+    pub benchmarks_marker: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TemplatesConfig {
+    /// Replaces the built-in solution template used by `scaffold`.
+    pub module: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: PathBuf::from("data"),
+            session_file: None,
+            offline: false,
+            bench: BenchConfig::default(),
+            output: OutputFormat::Pretty,
+            readme: ReadmeConfig::default(),
+            templates: TemplatesConfig::default(),
+            leaderboard_id: None,
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("README.md"),
+            benchmarks_marker: "<!--- benchmarking table --->".into(),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Self::Pretty),
+            "plain" => Ok(Self::Plain),
+            _ => Err(ConfigError::Invalid(format!(
+                "output format `{s}`, expected `pretty` or `plain`"
+            ))),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(PathBuf, io::Error),
+    Parse(String),
+    Invalid(String),
+}
+
+impl Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::IO(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            ConfigError::Parse(e) => write!(f, "config file is not valid TOML: {e}"),
+            ConfigError::Invalid(e) => write!(f, "invalid {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Returns the project configuration, loading it on first access.
+/// Exits the process if the configuration is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Failed to load configuration: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+impl Config {
+    /// Loads the config file (if present) and applies environment overrides.
+    pub fn load() -> Result<Self, ConfigError> {
+        let (path, explicit) = match env::var(ENV_CONFIG) {
+            Ok(path) => (PathBuf::from(path), true),
+            Err(_) => (PathBuf::from(DEFAULT_CONFIG_PATH), false),
+        };
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(contents) => Self::from_toml(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !explicit => Self::default(),
+            Err(e) => return Err(ConfigError::IO(path, e)),
+        };
+
+        config.apply_env(|key| env::var(key).ok())?;
+        Ok(config)
+    }
+
+    /// Parses a config file. Keys that are not present keep their default value.
+    pub fn from_toml(contents: &str) -> Result<Self, ConfigError> {
+        let table: Table = contents
+            .parse()
+            .map_err(|e: toml::de::Error| ConfigError::Parse(e.message().to_string()))?;
+
+        let mut config = Self::default();
+
+        if let Some(year) = get_int(&table, "year")? {
+            config.year = Some(to_year(year)?);
+        }
+        if let Some(dir) = get_str(&table, "data_dir")? {
+            config.data_dir = PathBuf::from(dir);
+        }
+        if let Some(file) = get_str(&table, "session_file")? {
+            config.session_file = Some(expand_home(file));
+        }
+        if let Some(format) = get_str(&table, "output")? {
+            config.output = format.parse()?;
+        }
+        if let Some(offline) = get_bool(&table, "offline")? {
+            config.offline = offline;
+        }
+
+        if let Some(bench) = get_table(&table, "bench")? {
+            if let Some(ms) = get_int(bench, "budget_ms")? {
+                config.bench.budget = Duration::from_millis(to_unsigned("bench.budget_ms", ms)?);
+            }
+            if let Some(n) = get_int(bench, "min_samples")? {
+                config.bench.min_samples = to_unsigned("bench.min_samples", n)?.into();
+            }
+            if let Some(n) = get_int(bench, "max_samples")? {
+                config.bench.max_samples = to_unsigned("bench.max_samples", n)?.into();
+            }
+            if config.bench.min_samples == 0 || config.bench.min_samples > config.bench.max_samples
+            {
+                return Err(ConfigError::Invalid(
+                    "bench samples, expected 0 < min_samples <= max_samples".into(),
+                ));
+            }
+        }
+
+        if let Some(readme) = get_table(&table, "readme")? {
+            if let Some(path) = get_str(readme, "path")? {
+                config.readme.path = PathBuf::from(path);
+            }
+            if let Some(marker) = get_str(readme, "benchmarks_marker")? {
+                config.readme.benchmarks_marker = marker.to_string();
+            }
+        }
+
+        if let Some(templates) = get_table(&table, "templates")? {
+            if let Some(module) = get_str(templates, "module")? {
+                config.templates.module = Some(PathBuf::from(module));
+            }
+        }
+
+        if let Some(leaderboard) = get_table(&table, "leaderboard")? {
+            if let Some(id) = get_int(leaderboard, "id")? {
+                config.leaderboard_id = Some(to_unsigned("leaderboard.id", id)?);
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        if let Some(year) = var(ENV_YEAR) {
+            let year = year
+                .parse()
+                .map_err(|_| ConfigError::Invalid(format!("{ENV_YEAR} `{year}`")))?;
+            self.year = Some(to_year(year)?);
+        }
+        if let Some(dir) = var(ENV_DATA_DIR) {
+            self.data_dir = PathBuf::from(dir);
+        }
+        if let Some(file) = var(ENV_SESSION_FILE) {
+            self.session_file = Some(expand_home(&file));
+        }
+        if let Some(ms) = var(ENV_BENCH_BUDGET_MS) {
+            let ms = ms
+                .parse()
+                .map_err(|_| ConfigError::Invalid(format!("{ENV_BENCH_BUDGET_MS} `{ms}`")))?;
+            self.bench.budget = Duration::from_millis(ms);
+        }
+        if let Some(format) = var(ENV_OUTPUT) {
+            self.output = format.parse()?;
+        }
+        if let Some(offline) = var(ENV_OFFLINE) {
+            self.offline = match offline.as_str() {
+                "1" | "true" => true,
+                "0" | "false" | "" => false,
+                _ => return Err(ConfigError::Invalid(format!("{ENV_OFFLINE} `{offline}`"))),
+            };
+        }
+        Ok(())
+    }
+
+    /// Path of a file inside a data folder, e.g. `data/inputs/01.txt`.
+    pub fn data_path(&self, folder: &str, file: &str) -> PathBuf {
+        self.data_dir.join(folder).join(file)
+    }
+}
+
+fn get_table<'a>(table: &'a Table, key: &str) -> Result<Option<&'a Table>, ConfigError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Table(t)) => Ok(Some(t)),
+        Some(_) => Err(ConfigError::Invalid(format!("`{key}`, expected a table"))),
+    }
+}
+
+fn get_str<'a>(table: &'a Table, key: &str) -> Result<Option<&'a str>, ConfigError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(ConfigError::Invalid(format!("`{key}`, expected a string"))),
+    }
+}
+
+fn get_int(table: &Table, key: &str) -> Result<Option<i64>, ConfigError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Integer(n)) => Ok(Some(*n)),
+        Some(_) => Err(ConfigError::Invalid(format!(
+            "`{key}`, expected an integer"
+        ))),
+    }
+}
+
+fn get_bool(table: &Table, key: &str) -> Result<Option<bool>, ConfigError> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Boolean(b)) => Ok(Some(*b)),
+        Some(_) => Err(ConfigError::Invalid(format!("`{key}`, expected a boolean"))),
+    }
+}
+
+fn to_year(year: i64) -> Result<u16, ConfigError> {
+    u16::try_from(year)
+        .ok()
+        .filter(|y| *y >= 2015)
+        .ok_or_else(|| ConfigError::Invalid(format!("year `{year}`, expected 2015 or later")))
+}
+
+fn to_unsigned(key: &str, n: i64) -> Result<u64, ConfigError> {
+    u64::try_from(n)
+        .map_err(|_| ConfigError::Invalid(format!("`{key}`, expected a positive value")))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Config, OutputFormat, ENV_DATA_DIR, ENV_OFFLINE, ENV_OUTPUT, ENV_YEAR};
+    #[allow(unused_imports)]
+    use std::{path::PathBuf, time::Duration};
+
+    #[test]
+    fn uses_defaults_for_empty_file() {
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_all_keys() {
+        let config = Config::from_toml(
+            r#"
+            year = 2023
+            data_dir = "puzzles"
+            session_file = "/tmp/session"
+            output = "plain"
+            offline = true
+
+            [bench]
+            budget_ms = 250
+            min_samples = 5
+            max_samples = 50
+
+            [readme]
+            path = "docs/README.md"
+            benchmarks_marker = "<!-- bench -->"
+
+            [templates]
+            module = "templates/day.rs"
+
+            [leaderboard]
+            id = 3031
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.data_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.session_file, Some(PathBuf::from("/tmp/session")));
+        assert_eq!(config.output, OutputFormat::Plain);
+        assert!(config.offline);
+        assert_eq!(config.bench.budget, Duration::from_millis(250));
+        assert_eq!(config.bench.min_samples, 5);
+        assert_eq!(config.bench.max_samples, 50);
+        assert_eq!(config.readme.path, PathBuf::from("docs/README.md"));
+        assert_eq!(config.readme.benchmarks_marker, "<!-- bench -->");
+        assert_eq!(
+            config.templates.module,
+            Some(PathBuf::from("templates/day.rs"))
+        );
+        assert_eq!(config.leaderboard_id, Some(3031));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(Config::from_toml("year = \"2024\"").is_err());
+        assert!(Config::from_toml("year = 1999").is_err());
+        assert!(Config::from_toml("output = \"json\"").is_err());
+        assert!(Config::from_toml("[bench]\nmin_samples = 0").is_err());
+        assert!(Config::from_toml("year = ").is_err());
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let mut config = Config::from_toml("year = 2023\ndata_dir = \"a\"").unwrap();
+        config
+            .apply_env(|key| match key {
+                ENV_YEAR => Some("2024".into()),
+                ENV_DATA_DIR => Some("b".into()),
+                ENV_OUTPUT => Some("plain".into()),
+                ENV_OFFLINE => Some("1".into()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.year, Some(2024));
+        assert_eq!(config.data_dir, PathBuf::from("b"));
+        assert_eq!(config.output, OutputFormat::Plain);
+        assert!(config.offline);
+    }
+}
@@ -6,7 +6,7 @@ use std::str::FromStr;
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::unlock::SERVER_UTC_OFFSET;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
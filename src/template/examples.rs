@@ -0,0 +1,123 @@
+/// Extracts example inputs from puzzle descriptions downloaded by aoc-cli.
+use std::{fs, io, path::Path};
+
+use crate::template::{config, Day};
+
+/// Returns the contents of all fenced code blocks that are introduced as an example,
+/// e.g. by a preceding "For example:" paragraph. Falls back to the first code block.
+pub fn extract(puzzle: &str) -> Vec<String> {
+    let mut blocks: Vec<(bool, String)> = vec![];
+    let mut current: Option<Vec<&str>> = None;
+    let mut last_text = "";
+
+    for line in puzzle.lines() {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match (&mut current, is_fence) {
+            (None, true) => current = Some(vec![]),
+            (Some(lines), true) => {
+                let is_example = last_text.to_lowercase().contains("example");
+                blocks.push((is_example, lines.join("\n")));
+                current = None;
+            }
+            (Some(lines), false) => lines.push(line),
+            (None, false) => {
+                if !line.trim().is_empty() {
+                    last_text = line;
+                }
+            }
+        }
+    }
+
+    let mut examples: Vec<String> = vec![];
+    for (_, block) in blocks.iter().filter(|(is_example, _)| *is_example) {
+        if !block.trim().is_empty() && !examples.contains(block) {
+            examples.push(block.clone());
+        }
+    }
+
+    if examples.is_empty() {
+        if let Some((_, block)) = blocks.into_iter().find(|(_, b)| !b.trim().is_empty()) {
+            examples.push(block);
+        }
+    }
+
+    examples
+}
+
+/// Writes examples extracted from the downloaded puzzle description of `day` to the examples folder.
+/// The first example goes to `NN.txt`, further examples to `NN-2.txt`, `NN-3.txt` and so on.
+/// Existing non-empty example files are left untouched. Returns the paths that were written.
+pub fn write_from_puzzle(day: Day) -> io::Result<Vec<String>> {
+    let config = config::get();
+    let puzzle = fs::read_to_string(config.data_path("puzzles", &format!("{day}.md")))?;
+
+    let mut written = vec![];
+    for (i, example) in extract(&puzzle).iter().enumerate() {
+        let name = if i == 0 {
+            format!("{day}.txt")
+        } else {
+            format!("{day}-{}.txt", i + 1)
+        };
+        let path = config.data_path("examples", &name);
+        if is_non_empty(&path) {
+            continue;
+        }
+        fs::write(&path, example)?;
+        written.push(path.to_string_lossy().into_owned());
+    }
+
+    Ok(written)
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::extract;
+
+    #[test]
+    fn extracts_example_blocks() {
+        let puzzle = [
+            "\\--- Day 1: Historian Hysteria ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "Pair up the numbers:",
+            "",
+            "```",
+            "1 3",
+            "```",
+            "",
+            "Here is a larger example:",
+            "",
+            "```",
+            "1   2",
+            "```",
+        ]
+        .join("\n");
+
+        assert_eq!(extract(&puzzle), vec!["3   4\n4   3", "1   2"]);
+    }
+
+    #[test]
+    fn falls_back_to_first_block() {
+        let puzzle = "Consider this map:\n\n```\n..#\n#..\n```\n";
+        assert_eq!(extract(puzzle), vec!["..#\n#.."]);
+    }
+
+    #[test]
+    fn handles_missing_blocks() {
+        assert!(extract("no code here").is_empty());
+    }
+}
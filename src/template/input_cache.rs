@@ -0,0 +1,233 @@
+/// Validates downloaded puzzle inputs and keeps track of their checksums,
+/// so valid inputs are not fetched twice and corrupted ones are detected.
+use std::{collections::HashMap, fmt::Display, fs, io, path::PathBuf, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{config, Day};
+
+static CHECKSUMS_FILE_NAME: &str = ".checksums.json";
+
+/// Reasons for rejecting an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Empty,
+    /// The server returned an HTML page instead of the input.
+    Html,
+    /// The session cookie is missing or expired.
+    LoginRequired,
+    /// The puzzle has not unlocked yet.
+    NotUnlocked,
+    /// The input does not end with a newline, which every puzzle input does.
+    Truncated,
+    /// The input differs from the one that was downloaded.
+    ChecksumMismatch,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "input is empty."),
+            InputError::Html => write!(f, "input is an HTML page, not a puzzle input."),
+            InputError::LoginRequired => {
+                write!(f, "input is a login prompt. Is the session cookie valid?")
+            }
+            InputError::NotUnlocked => write!(f, "puzzle has not unlocked yet."),
+            InputError::Truncated => write!(f, "input seems truncated."),
+            InputError::ChecksumMismatch => {
+                write!(f, "input changed since it was downloaded.")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputStatus {
+    Missing,
+    Valid,
+    Invalid(InputError),
+}
+
+/// Checks a freshly downloaded input for common failure modes.
+pub fn validate(raw: &str) -> Result<(), InputError> {
+    if raw.trim().is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    let trimmed = raw.trim_start();
+    let head = trimmed.get(..100).unwrap_or(trimmed).to_lowercase();
+    if head.starts_with("<!doctype") || head.starts_with("<html") {
+        return Err(InputError::Html);
+    }
+    if raw.contains("Please log in") || raw.contains("Puzzle inputs differ by user") {
+        return Err(InputError::LoginRequired);
+    }
+    if raw.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(InputError::NotUnlocked);
+    }
+    if !raw.ends_with('\n') {
+        return Err(InputError::Truncated);
+    }
+
+    Ok(())
+}
+
+/// Normalizes line endings to `\n` and trailing whitespace to exactly one newline.
+pub fn normalize(raw: &str) -> String {
+    let mut normalized = raw.replace("\r\n", "\n").trim_end().to_string();
+    normalized.push('\n');
+    normalized
+}
+
+/// 64-bit FNV-1a hash of the input, formatted as hex.
+pub fn checksum(contents: &str) -> String {
+    let hash = contents.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn input_path(day: Day) -> PathBuf {
+    config::get().data_path("inputs", &format!("{day}.txt"))
+}
+
+fn checksums_path() -> PathBuf {
+    config::get().data_path("inputs", CHECKSUMS_FILE_NAME)
+}
+
+/// Returns the status of the cached input of `day`.
+pub fn status(day: Day) -> InputStatus {
+    let Ok(contents) = fs::read_to_string(input_path(day)) else {
+        return InputStatus::Missing;
+    };
+
+    // scaffolded days start with an empty input file.
+    if contents.is_empty() {
+        return InputStatus::Missing;
+    }
+
+    if let Err(e) = validate(&contents) {
+        return InputStatus::Invalid(e);
+    }
+
+    match read_checksums().get(&day.to_string()) {
+        Some(stored) if *stored != checksum(&contents) => {
+            InputStatus::Invalid(InputError::ChecksumMismatch)
+        }
+        _ => InputStatus::Valid,
+    }
+}
+
+/// Validates a downloaded input, then writes it normalized to the input file of `day` and records its checksum.
+/// The existing input is left untouched if validation fails.
+pub fn store(day: Day, raw: &str) -> Result<PathBuf, CacheError> {
+    validate(raw).map_err(CacheError::Input)?;
+
+    let contents = normalize(raw);
+    let path = input_path(day);
+    fs::write(&path, &contents)?;
+
+    let mut checksums = read_checksums();
+    checksums.insert(day.to_string(), checksum(&contents));
+    write_checksums(&checksums)?;
+
+    Ok(path)
+}
+
+fn read_checksums() -> HashMap<String, String> {
+    fs::read_to_string(checksums_path())
+        .ok()
+        .and_then(|s| JsonValue::from_str(&s).ok())
+        .and_then(|json| {
+            json.get::<HashMap<String, JsonValue>>().map(|map| {
+                map.iter()
+                    .filter_map(|(k, v)| v.get::<String>().map(|v| (k.clone(), v.clone())))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn write_checksums(checksums: &HashMap<String, String>) -> Result<(), io::Error> {
+    let json = JsonValue::Object(
+        checksums
+            .iter()
+            .map(|(k, v)| (k.clone(), JsonValue::String(v.clone())))
+            .collect(),
+    );
+    let mut file = fs::File::create(checksums_path())?;
+    json.format_to(&mut file)
+}
+
+#[derive(Debug)]
+pub enum CacheError {
+    Input(InputError),
+    IO(io::Error),
+}
+
+impl From<io::Error> for CacheError {
+    fn from(e: io::Error) -> Self {
+        CacheError::IO(e)
+    }
+}
+
+impl Display for CacheError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheError::Input(e) => write!(f, "{e}"),
+            CacheError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{checksum, normalize, validate, InputError};
+
+    #[test]
+    fn accepts_puzzle_input() {
+        assert_eq!(validate("3   4\n4   3\n"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_error_pages() {
+        assert_eq!(validate(""), Err(InputError::Empty));
+        assert_eq!(validate("\n\n"), Err(InputError::Empty));
+        assert_eq!(
+            validate("<!DOCTYPE html>\n<html></html>\n"),
+            Err(InputError::Html)
+        );
+        assert_eq!(
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(InputError::LoginRequired)
+        );
+        assert_eq!(
+            validate("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"),
+            Err(InputError::NotUnlocked)
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        assert_eq!(validate("3   4\n4   "), Err(InputError::Truncated));
+    }
+
+    #[test]
+    fn normalizes_trailing_newlines() {
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb\n");
+        assert_eq!(normalize("a\n\n\n"), "a\n");
+    }
+
+    #[test]
+    fn computes_stable_checksums() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum("1\n2\n"), checksum("1\n2"));
+    }
+}
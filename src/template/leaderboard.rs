@@ -0,0 +1,508 @@
+/// Private leaderboard model, scoring and rendering.
+/// The JSON document is provided by a [`LeaderboardSource`], which is either the website or a local file.
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::{Display, Write},
+    fs,
+    io::{self, Write as _},
+    path::PathBuf,
+    process::{Command, Stdio},
+    str::FromStr,
+    time::Duration,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::unlock::{format_countdown, unlock_timestamp};
+use crate::template::{all_days, ansi, config, Day, ANSI_BOLD, ANSI_RESET};
+
+#[derive(Debug)]
+pub enum LeaderboardError {
+    IO(io::Error),
+    Fetch(String),
+    Parse(String),
+}
+
+impl From<io::Error> for LeaderboardError {
+    fn from(e: io::Error) -> Self {
+        LeaderboardError::IO(e)
+    }
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderboardError::IO(e) => write!(f, "{e}"),
+            LeaderboardError::Fetch(e) => write!(f, "could not fetch leaderboard: {e}"),
+            LeaderboardError::Parse(e) => write!(f, "could not parse leaderboard: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Provides the raw JSON document of a private leaderboard.
+pub trait LeaderboardSource {
+    fn fetch(&self) -> Result<String, LeaderboardError>;
+}
+
+/// Fetches the leaderboard from adventofcode.com with the session cookie, via `curl`.
+pub struct HttpSource {
+    pub year: u16,
+    pub id: u64,
+    pub session_file: PathBuf,
+}
+
+/// Reads a leaderboard from a JSON file, e.g. a previously cached response.
+pub struct FileSource {
+    pub path: PathBuf,
+}
+
+impl HttpSource {
+    /// Builds a source from the project configuration.
+    /// Falls back to aoc-cli's default session file at `~/.adventofcode.session`.
+    pub fn from_config(year: u16, id: u64) -> Self {
+        let session_file = config::get().session_file.clone().unwrap_or_else(|| {
+            PathBuf::from(env::var_os("HOME").unwrap_or_default()).join(".adventofcode.session")
+        });
+        Self {
+            year,
+            id,
+            session_file,
+        }
+    }
+}
+
+impl LeaderboardSource for HttpSource {
+    fn fetch(&self) -> Result<String, LeaderboardError> {
+        let session = fs::read_to_string(&self.session_file).map_err(|e| {
+            LeaderboardError::Fetch(format!(
+                "could not read session cookie from {}: {e}",
+                self.session_file.display()
+            ))
+        })?;
+
+        let url = format!(
+            "https://adventofcode.com/{}/leaderboard/private/view/{}.json",
+            self.year, self.id
+        );
+
+        let curl_err = |e: io::Error| LeaderboardError::Fetch(format!("could not call curl: {e}"));
+
+        // the cookie is passed as a config on stdin, so the session does not show up in the process list.
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--location"])
+            .args(["--user-agent", "github.com/fspoettel/advent-of-code-rust"])
+            .args(["--config", "-"])
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(curl_err)?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(curl_cookie_config(session.trim()).as_bytes())
+                .map_err(curl_err)?;
+        }
+
+        let output = child.wait_with_output().map_err(curl_err)?;
+
+        if !output.status.success() {
+            return Err(LeaderboardError::Fetch(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// A curl config line that sends the session cookie, quoted as curl's config syntax expects.
+fn curl_cookie_config(session: &str) -> String {
+    let escaped = session.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("cookie = \"session={escaped}\"\n")
+}
+
+impl LeaderboardSource for FileSource {
+    fn fetch(&self) -> Result<String, LeaderboardError> {
+        Ok(fs::read_to_string(&self.path)?)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Timestamps (unix seconds) at which a member got the stars of a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DayCompletion {
+    pub part_1: Option<i64>,
+    pub part_2: Option<i64>,
+}
+
+impl DayCompletion {
+    /// Time between getting the first and the second star.
+    pub fn delta(&self) -> Option<i64> {
+        Some(self.part_2? - self.part_1?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    /// Score as reported by the server.
+    pub local_score: u32,
+    pub completion: BTreeMap<Day, DayCompletion>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn load(source: &impl LeaderboardSource) -> Result<Self, LeaderboardError> {
+        Self::from_json(&source.fetch()?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, LeaderboardError> {
+        let parse_err = |msg: &str| LeaderboardError::Parse(msg.to_string());
+
+        let json = JsonValue::from_str(s).map_err(|_| parse_err("not valid JSON."))?;
+        let root = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or_else(|| parse_err("expected document to be an object."))?;
+
+        let year = root
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| parse_err("expected `event` to be a year."))?;
+
+        let members = root
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or_else(|| parse_err("expected `members` to be an object."))?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| parse_err(&e))?;
+
+        Ok(Leaderboard { year, members })
+    }
+
+    /// Computes local scores from star timestamps:
+    /// for every star, a member gets one point per member who got it later or not at all.
+    pub fn local_scores(&self) -> HashMap<u64, u32> {
+        let n = self.members.len() as u32;
+        let mut scores: HashMap<u64, u32> = self.members.iter().map(|m| (m.id, 0)).collect();
+
+        for day in all_days() {
+            for part in [1, 2] {
+                let mut finishers: Vec<(i64, u64)> = self
+                    .members
+                    .iter()
+                    .filter_map(|m| {
+                        let completion = m.completion.get(&day)?;
+                        let ts = if part == 1 {
+                            completion.part_1
+                        } else {
+                            completion.part_2
+                        };
+                        Some((ts?, m.id))
+                    })
+                    .collect();
+                finishers.sort_unstable();
+
+                for (rank, (_, id)) in finishers.iter().enumerate() {
+                    *scores.entry(*id).or_default() += n - rank as u32;
+                }
+            }
+        }
+
+        scores
+    }
+
+    /// Members ordered by computed local score, then by stars and by who got the last star first.
+    pub fn rankings(&self) -> Vec<(u32, &Member)> {
+        let scores = self.local_scores();
+        let mut ranked: Vec<(u32, &Member)> = self
+            .members
+            .iter()
+            .map(|m| (scores.get(&m.id).copied().unwrap_or(0), m))
+            .collect();
+
+        ranked.sort_by(|(score_a, a), (score_b, b)| {
+            score_b
+                .cmp(score_a)
+                .then(b.stars.cmp(&a.stars))
+                .then(last_star(a).cmp(&last_star(b)))
+                .then(a.id.cmp(&b.id))
+        });
+
+        ranked
+    }
+
+    /// Renders the overall ranking with a star grid per day.
+    pub fn render_rankings(&self) -> String {
+        let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+        let mut out = format!("{bold}Private leaderboard {}{reset}\n\n", self.year);
+
+        let ranked = self.rankings();
+        let tens: String = all_days()
+            .map(|d| match d.into_inner() / 10 {
+                0 => ' ',
+                n => char::from(b'0' + n),
+            })
+            .collect();
+        let ones: String = all_days()
+            .map(|d| char::from(b'0' + d.into_inner() % 10))
+            .collect();
+        let _ = writeln!(out, "{:>4} {:>6}  {tens}", "", "");
+        let _ = writeln!(out, "{:>4} {:>6}  {ones}", "", "Score");
+
+        for (rank, (score, member)) in ranked.iter().enumerate() {
+            let stars: String = all_days()
+                .map(|day| match member.completion.get(&day) {
+                    Some(DayCompletion {
+                        part_2: Some(_), ..
+                    }) => '★',
+                    Some(DayCompletion {
+                        part_1: Some(_), ..
+                    }) => '☆',
+                    _ => '·',
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "{:>3}) {score:>6}  {stars}  {}",
+                rank + 1,
+                member.display_name()
+            );
+        }
+
+        out.trim_end().to_string()
+    }
+
+    /// Renders the star timestamps of a day relative to its unlock, and the delta between both parts.
+    pub fn render_day(&self, day: Day) -> String {
+        let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+        let unlock = unlock_timestamp(i32::from(self.year), day);
+        let mut out = format!("{bold}Day {day}, {}{reset}\n\n", self.year);
+
+        let mut finishers: Vec<(&Member, DayCompletion)> = self
+            .members
+            .iter()
+            .filter_map(|m| Some((m, *m.completion.get(&day)?)))
+            .collect();
+        finishers.sort_by_key(|(m, c)| (c.part_2.is_none(), c.part_2, c.part_1, m.id));
+
+        let _ = writeln!(
+            out,
+            "{:>4} {:>12} {:>12} {:>12}  Name",
+            "", "Part 1", "Part 2", "Delta"
+        );
+        for (i, (member, completion)) in finishers.iter().enumerate() {
+            let fmt = |secs: Option<i64>| {
+                secs.map_or_else(
+                    || "-".into(),
+                    |secs| format_countdown(Duration::from_secs(u64::try_from(secs).unwrap_or(0))),
+                )
+            };
+            let _ = writeln!(
+                out,
+                "{:>3}) {:>12} {:>12} {:>12}  {}",
+                i + 1,
+                fmt(completion.part_1.map(|ts| ts - unlock)),
+                fmt(completion.part_2.map(|ts| ts - unlock)),
+                fmt(completion.delta()),
+                member.display_name()
+            );
+        }
+
+        if finishers.is_empty() {
+            out.push_str("No stars yet.");
+        }
+
+        out.trim_end().to_string()
+    }
+}
+
+fn last_star(member: &Member) -> i64 {
+    member
+        .completion
+        .values()
+        .flat_map(|c| [c.part_1, c.part_2])
+        .flatten()
+        .max()
+        .unwrap_or(i64::MAX)
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).copied();
+
+        let id = number("id").ok_or("Expected member.id to be a number.")? as u64;
+        let name = json.get("name").and_then(|v| v.get::<String>()).cloned();
+        let stars = number("stars").unwrap_or(0.0) as u32;
+        let local_score = number("local_score").unwrap_or(0.0) as u32;
+
+        let mut completion = BTreeMap::new();
+        if let Some(days) = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+        {
+            for (day, parts) in days {
+                let day: Day = day
+                    .parse()
+                    .map_err(|_| format!("Expected `{day}` to be a day."))?;
+                let parts = parts
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected completion_day_level entries to be objects.")?;
+                let star = |part: &str| {
+                    parts
+                        .get(part)
+                        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                        .and_then(|v| v.get("get_star_ts"))
+                        .and_then(|v| v.get::<f64>())
+                        .map(|ts| *ts as i64)
+                };
+                completion.insert(
+                    day,
+                    DayCompletion {
+                        part_1: star("1"),
+                        part_2: star("2"),
+                    },
+                );
+            }
+        }
+
+        Ok(Member {
+            id,
+            name,
+            stars,
+            local_score,
+            completion,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{curl_cookie_config, Leaderboard, LeaderboardError, LeaderboardSource};
+    #[allow(unused_imports)]
+    use crate::day;
+
+    #[allow(dead_code)]
+    struct FixtureSource(&'static str);
+
+    impl LeaderboardSource for FixtureSource {
+        fn fetch(&self) -> Result<String, LeaderboardError> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    // day 1 unlocks at 1733029200 in 2024.
+    #[allow(dead_code)]
+    const FIXTURE: &str = r#"{
+        "owner_id": 1,
+        "event": "2024",
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 8, "global_score": 0, "last_star_ts": 1733116000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733029500, "star_index": 1 }, "2": { "get_star_ts": 1733030000, "star_index": 2 } },
+                    "2": { "1": { "get_star_ts": 1733116000, "star_index": 5 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 5, "global_score": 0, "last_star_ts": 1733029900,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733029400, "star_index": 0 }, "2": { "get_star_ts": 1733029900, "star_index": 3 } }
+                }
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn loads_from_source() {
+        let leaderboard = Leaderboard::load(&FixtureSource(FIXTURE)).unwrap();
+        assert_eq!(leaderboard.year, 2024);
+        assert_eq!(leaderboard.members.len(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        assert!(Leaderboard::load(&FixtureSource("{}")).is_err());
+        assert!(Leaderboard::load(&FixtureSource("<html>")).is_err());
+    }
+
+    #[test]
+    fn computes_local_scores() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        let scores = leaderboard.local_scores();
+        // day 1: #2 gets both stars first (3 + 3), alice second (2 + 2). day 2: alice is alone (3).
+        assert_eq!(scores[&1], 7);
+        assert_eq!(scores[&2], 6);
+        assert_eq!(scores[&3], 0);
+    }
+
+    #[test]
+    fn ranks_members_by_score() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        let ids: Vec<u64> = leaderboard.rankings().iter().map(|(_, m)| m.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn computes_part_deltas() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        let alice = leaderboard.members.iter().find(|m| m.id == 1).unwrap();
+        assert_eq!(alice.completion[&day!(1)].delta(), Some(500));
+        assert_eq!(alice.completion[&day!(2)].delta(), None);
+    }
+
+    #[test]
+    fn renders_day_relative_to_unlock() {
+        let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+        let out = leaderboard.render_day(day!(1));
+        assert!(out.contains("00:03:20     00:11:40     00:08:20  (anonymous user #2)"));
+    }
+
+    #[test]
+    fn quotes_session_in_curl_config() {
+        assert_eq!(
+            curl_cookie_config("53616c74"),
+            "cookie = \"session=53616c74\"\n"
+        );
+        assert_eq!(
+            curl_cookie_config("a\"b\\c"),
+            "cookie = \"session=a\\\"b\\\\c\"\n"
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod input_cache;
+pub mod leaderboard;
 pub mod runner;
+pub mod unlock;
 
 pub use day::*;
 
@@ -15,11 +21,20 @@ pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the given escape code, or an empty string if the configured output format is plain.
+#[must_use]
+pub fn ansi(code: &'static str) -> &'static str {
+    match config::get().output {
+        config::OutputFormat::Pretty => code,
+        config::OutputFormat::Plain => "",
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(config::get().data_path(folder, &format!("{day}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -28,10 +43,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(config::get().data_path(folder, &format!("{day}-{part}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
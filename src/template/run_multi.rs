@@ -1,6 +1,6 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ansi, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
@@ -11,6 +11,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
+    let (bold, italic, reset) = (ansi(ANSI_BOLD), ansi(ANSI_ITALIC), ansi(ANSI_RESET));
 
     // NOTE: use non-duplicate, sorted day values.
     all_days()
@@ -21,7 +22,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("{bold}Day {day}{reset}");
             println!("------");
 
             let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
@@ -37,9 +38,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        println!("\n{bold}Total (Run):{reset} {italic}{total_millis:.2}ms{reset}");
         Some(timings)
     } else {
         None
@@ -68,7 +67,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{config, Day};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -90,6 +89,10 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if config::get().offline {
+            args.push("--offline");
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
@@ -174,7 +177,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -6,14 +6,15 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ansi, aoc_cli, config, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::trace;
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(func, input, &part_str, |result| {
+        print_result(result, &part_str, "")
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
@@ -24,15 +25,19 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. the configured time budget or the minimum number of samples, whatever takes longer.)
+///
+/// Only the first execution is traced, in a span named `label`.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    label: &str,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
+        let _span = trace::span(label);
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
@@ -44,7 +49,7 @@ fn run_timed<I: Clone, T>(
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        trace::muted(|| bench(func, input, &base_time))
     } else {
         (base_time, 1)
     };
@@ -55,11 +60,12 @@ fn run_timed<I: Clone, T>(
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    print!(" > {}benching{}", ansi(ANSI_ITALIC), ansi(ANSI_RESET));
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench = &config::get().bench;
+    let bench_iterations = (bench.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(bench.min_samples, bench.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -109,7 +115,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+                let str = format!("{part}: {bold}{result}{reset}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -159,6 +166,11 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if config::get().offline {
+        eprintln!("Offline mode: not submitting result.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -0,0 +1,215 @@
+/// Computes when puzzles unlock. Puzzles unlock at midnight server time (UTC-5) from the 1st to the 25th of december.
+///
+/// Timestamps are computed without chrono, so the leaderboard works without the `today` feature.
+use std::time::Duration;
+
+#[cfg(feature = "today")]
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+
+use crate::template::Day;
+
+pub const SERVER_UTC_OFFSET: i32 = -5;
+
+const SERVER_UTC_OFFSET_SECS: i64 = SERVER_UTC_OFFSET as i64 * 3600;
+
+/// Unix timestamp of the unlock of `day` in the given year, i.e. 05:00 UTC.
+pub fn unlock_timestamp(year: i32, day: Day) -> i64 {
+    days_from_civil(i64::from(year), 12, i64::from(day.into_inner())) * 86400
+        - SERVER_UTC_OFFSET_SECS
+}
+
+/// Year of the latest event at `now` (unix seconds): the current year from december 1st on, the previous one before.
+pub fn latest_event_year(now: i64) -> u16 {
+    let (year, month) = civil_from_days((now + SERVER_UTC_OFFSET_SECS).div_euclid(86400));
+    if month == 12 {
+        year as u16
+    } else {
+        (year - 1) as u16
+    }
+}
+
+/// Days since 1970-01-01 for a date of the proleptic gregorian calendar.
+/// See: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`], returns year and month only.
+fn civil_from_days(days: i64) -> (i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month)
+}
+
+#[cfg(feature = "today")]
+fn server_offset() -> FixedOffset {
+    // NOTE: the offset is a small constant and always in range.
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
+}
+
+/// Returns the moment the puzzle of `day` unlocks in the given year.
+#[cfg(feature = "today")]
+pub fn unlock_time(year: i32, day: Day) -> DateTime<Utc> {
+    DateTime::from_timestamp(unlock_timestamp(year, day), 0)
+        .expect("unlock times of four-digit years are in range")
+}
+
+/// Returns the next puzzle to unlock strictly after `now`, together with its unlock time.
+#[cfg(feature = "today")]
+pub fn next_unlock(now: DateTime<Utc>) -> (i32, Day, DateTime<Utc>) {
+    let server_year = now.with_timezone(&server_offset()).year();
+
+    for year in [server_year, server_year + 1] {
+        if let Some((day, time)) = crate::template::all_days()
+            .map(|day| (day, unlock_time(year, day)))
+            .find(|(_, time)| *time > now)
+        {
+            return (year, day, time);
+        }
+    }
+
+    unreachable!("the first puzzle of next year always unlocks in the future")
+}
+
+/// Returns the puzzle of the current day in server time if it is between the 1st and the 25th of december,
+/// and the next puzzle to unlock otherwise. Unlike [`next_unlock`], a puzzle that just unlocked is not skipped.
+#[cfg(feature = "today")]
+pub fn current_or_next_unlock(now: DateTime<Utc>) -> (i32, Day, DateTime<Utc>) {
+    let server_now = now.with_timezone(&server_offset());
+    let today = (server_now.month() == 12)
+        .then(|| u8::try_from(server_now.day()).ok().and_then(Day::new))
+        .flatten();
+
+    match today {
+        Some(day) => (server_now.year(), day, unlock_time(server_now.year(), day)),
+        None => next_unlock(now),
+    }
+}
+
+/// Formats a remaining duration as `HH:MM:SS`, prefixed with the number of days if longer than a day.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86400,
+        (secs % 86400) / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[cfg(feature = "today")]
+    #[allow(unused_imports)]
+    use super::{current_or_next_unlock, next_unlock, unlock_time};
+    #[allow(unused_imports)]
+    use super::{format_countdown, latest_event_year, unlock_timestamp};
+    #[allow(unused_imports)]
+    use crate::day;
+    #[cfg(feature = "today")]
+    #[allow(unused_imports)]
+    use chrono::{TimeZone, Utc};
+    #[allow(unused_imports)]
+    use std::time::Duration;
+
+    #[test]
+    fn computes_unlock_timestamps() {
+        assert_eq!(unlock_timestamp(2024, day!(1)), 1_733_029_200);
+        assert_eq!(unlock_timestamp(2024, day!(6)), 1_733_461_200);
+    }
+
+    #[test]
+    fn finds_latest_event_year() {
+        // 2024-12-01 04:59 UTC is still november in server time.
+        assert_eq!(latest_event_year(1_733_029_140), 2023);
+        assert_eq!(latest_event_year(1_733_029_200), 2024);
+        // 2025-06-01 00:00 UTC.
+        assert_eq!(latest_event_year(1_748_736_000), 2024);
+    }
+
+    #[test]
+    #[cfg(feature = "today")]
+    fn unlocks_at_midnight_server_time() {
+        let expected = Utc.with_ymd_and_hms(2024, 12, 6, 5, 0, 0).unwrap();
+        assert_eq!(unlock_time(2024, day!(6)), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "today")]
+    fn finds_first_day_before_december() {
+        let now = Utc.with_ymd_and_hms(2024, 11, 30, 12, 0, 0).unwrap();
+        assert_eq!(
+            next_unlock(now),
+            (2024, day!(1), unlock_time(2024, day!(1)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "today")]
+    fn finds_next_day_during_advent() {
+        // 23:59 server time on december 5th.
+        let now = Utc.with_ymd_and_hms(2024, 12, 6, 4, 59, 0).unwrap();
+        assert_eq!(
+            next_unlock(now),
+            (2024, day!(6), unlock_time(2024, day!(6)))
+        );
+
+        // right at unlock, the next puzzle is tomorrow's.
+        let now = unlock_time(2024, day!(6));
+        assert_eq!(next_unlock(now).1, day!(7));
+    }
+
+    #[test]
+    #[cfg(feature = "today")]
+    fn keeps_puzzle_that_just_unlocked() {
+        // a few minutes after midnight server time on december 6th.
+        let now = unlock_time(2024, day!(6)) + chrono::Duration::minutes(3);
+        assert_eq!(
+            current_or_next_unlock(now),
+            (2024, day!(6), unlock_time(2024, day!(6)))
+        );
+
+        // before december, and after the 25th, the next puzzle is counted down to.
+        let now = Utc.with_ymd_and_hms(2024, 11, 30, 12, 0, 0).unwrap();
+        assert_eq!(current_or_next_unlock(now), next_unlock(now));
+        let now = Utc.with_ymd_and_hms(2024, 12, 26, 6, 0, 0).unwrap();
+        assert_eq!(current_or_next_unlock(now).0, 2025);
+    }
+
+    #[test]
+    #[cfg(feature = "today")]
+    fn rolls_over_to_next_year() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 25, 6, 0, 0).unwrap();
+        assert_eq!(
+            next_unlock(now),
+            (2025, day!(1), unlock_time(2025, day!(1)))
+        );
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_countdown(Duration::from_secs(90061)), "1d 01:01:01");
+    }
+}
@@ -0,0 +1,344 @@
+/// Runtime tracing of solution steps, as a replacement for temporary `println!` debugging.
+///
+/// Events and spans are only written when the solution runs with `--trace` or `--trace=<level>`,
+/// which `cargo solve <day> --trace` forwards. Otherwise [`trace!`](crate::trace!) does not even format
+/// its arguments. Output goes to stderr, or to the file given with `--trace-file <path>`:
+///
+/// ```
+/// # use advent_of_code::trace;
+/// let _span = trace::span("parse");
+/// for line in "1\n2".lines() {
+///     advent_of_code::trace!("read {line}");
+/// }
+/// advent_of_code::trace!(Info, "parsed {} lines", 2);
+/// ```
+///
+/// Events are indented by the spans they happen in. When a span ends, it reports its duration and
+/// the number of events inside it. The runner opens a span around every part.
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How detailed an event is. A level enables itself and all levels before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    #[default]
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLevelError(String);
+
+impl Display for ParseLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown trace level `{}`, expected one of error, warn, info, debug, trace",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseLevelError {}
+
+impl FromStr for Level {
+    type Err = ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseLevelError(s.to_string()))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Tracing options of a solution run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    level: Level,
+    file: Option<PathBuf>,
+}
+
+/// Reads `--trace`, `--trace=<level>` and `--trace-file <path>` from the arguments of a solution.
+/// `--trace-file` alone traces at the default level.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, ParseLevelError> {
+    let mut level = None;
+    let mut file = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--trace" {
+            level = Some(Level::default());
+        } else if let Some(value) = arg.strip_prefix("--trace=") {
+            level = Some(value.parse()?);
+        } else if arg == "--trace-file" {
+            file = args.next().map(PathBuf::from);
+        } else if let Some(value) = arg.strip_prefix("--trace-file=") {
+            file = Some(PathBuf::from(value));
+        }
+    }
+    let level = level.or_else(|| file.is_some().then(Level::default));
+    Ok(level.map(|level| Options { level, file }))
+}
+
+struct Tracer {
+    level: Level,
+    start: Instant,
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Tracer {
+    fn new(options: Options) -> Self {
+        let out: Box<dyn Write + Send> = match options.file.map(File::create) {
+            None => Box::new(io::stderr()),
+            Some(Ok(file)) => Box::new(LineWriter::new(file)),
+            Some(Err(e)) => {
+                eprintln!("could not open trace file, tracing to stderr: {e}");
+                Box::new(io::stderr())
+            }
+        };
+        Self {
+            level: options.level,
+            start: Instant::now(),
+            out: Mutex::new(out),
+        }
+    }
+
+    fn write(&self, depth: usize, message: fmt::Arguments) {
+        let line = format_line(self.start.elapsed(), depth, message);
+        if let Ok(mut out) = self.out.lock() {
+            let _ = out.write_all(line.as_bytes());
+        }
+    }
+}
+
+fn tracer() -> Option<&'static Tracer> {
+    static TRACER: OnceLock<Option<Tracer>> = OnceLock::new();
+    TRACER
+        .get_or_init(|| {
+            let options = parse_args(std::env::args()).unwrap_or_else(|e| {
+                eprintln!("{e}, tracing at the default level");
+                Some(Options {
+                    level: Level::default(),
+                    file: None,
+                })
+            });
+            options.map(Tracer::new)
+        })
+        .as_ref()
+}
+
+/// A line of output: the time since tracing started, one guide per open span and the message.
+fn format_line(elapsed: Duration, depth: usize, message: fmt::Arguments) -> String {
+    format!(
+        "[{:>10.3}ms] {}{message}\n",
+        elapsed.as_secs_f64() * 1000.0,
+        "│ ".repeat(depth)
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+struct OpenSpan {
+    name: String,
+    start: Instant,
+    events: usize,
+}
+
+thread_local! {
+    static SPANS: RefCell<Vec<OpenSpan>> = const { RefCell::new(Vec::new()) };
+    static MUTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether events of `level` are written.
+pub fn enabled(level: Level) -> bool {
+    tracer().is_some_and(|tracer| level <= tracer.level) && !MUTED.get()
+}
+
+/// Writes an event. Use [`trace!`](crate::trace!) instead, which skips formatting when tracing is disabled.
+#[doc(hidden)]
+pub fn event(level: Level, message: fmt::Arguments) {
+    let Some(tracer) = tracer().filter(|_| enabled(level)) else {
+        return;
+    };
+    let depth = SPANS.with_borrow_mut(|spans| {
+        for span in spans.iter_mut() {
+            span.events += 1;
+        }
+        spans.len()
+    });
+    tracer.write(depth, format_args!("{level:<5} {message}"));
+}
+
+/// Ends the span it was created by when dropped.
+#[must_use = "the span ends when this guard is dropped"]
+pub struct Span {
+    open: bool,
+}
+
+/// Starts a span that groups the events until the returned guard is dropped.
+/// Spans are written at [`Level::Info`].
+pub fn span(name: impl Display) -> Span {
+    let Some(tracer) = tracer().filter(|_| enabled(Level::Info)) else {
+        return Span { open: false };
+    };
+    let name = name.to_string();
+    let depth = SPANS.with_borrow(Vec::len);
+    tracer.write(depth, format_args!("┌ {name}"));
+    SPANS.with_borrow_mut(|spans| {
+        spans.push(OpenSpan {
+            name,
+            start: Instant::now(),
+            events: 0,
+        })
+    });
+    Span { open: true }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if !self.open {
+            return;
+        }
+        let (Some(tracer), Some((span, depth))) = (
+            tracer(),
+            SPANS.with_borrow_mut(|spans| spans.pop().map(|span| (span, spans.len()))),
+        ) else {
+            return;
+        };
+        tracer.write(
+            depth,
+            format_args!(
+                "└ {} ({:.1?}, {} events)",
+                span.name,
+                span.start.elapsed(),
+                span.events
+            ),
+        );
+    }
+}
+
+/// Runs `f` without writing any events, e.g. while benchmarking.
+pub fn muted<R>(f: impl FnOnce() -> R) -> R {
+    let was_muted = MUTED.replace(true);
+    let result = f();
+    MUTED.set(was_muted);
+    result
+}
+
+/// Writes an event if tracing is enabled at the given level, which defaults to `Debug`.
+///
+/// ```
+/// # use advent_of_code::trace;
+/// let (x, y) = (3, 4);
+/// trace!("at {x},{y}");
+/// trace!(Trace, "step {}", x + y);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::event($crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+    ($($arg:tt)+) => {
+        $crate::trace!(Debug, $($arg)+)
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{format_line, parse_args, Level, Options, ParseLevelError};
+    #[allow(unused_imports)]
+    use std::{path::PathBuf, time::Duration};
+
+    #[allow(dead_code)]
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!("DEBUG".parse(), Ok(Level::Debug));
+        assert_eq!("warn".parse(), Ok(Level::Warn));
+        assert_eq!(
+            "verbose".parse::<Level>(),
+            Err(ParseLevelError("verbose".into()))
+        );
+        assert!(Level::Error < Level::Info && Level::Info < Level::Trace);
+        assert_eq!(format!("[{:<5}]", Level::Info), "[info ]");
+    }
+
+    #[test]
+    fn reads_trace_arguments() {
+        assert_eq!(parse_args(args("--time --submit 1")), Ok(None));
+        assert_eq!(
+            parse_args(args("--trace")),
+            Ok(Some(Options {
+                level: Level::Debug,
+                file: None
+            }))
+        );
+        assert_eq!(
+            parse_args(args("--trace=info --trace-file trace.log")),
+            Ok(Some(Options {
+                level: Level::Info,
+                file: Some(PathBuf::from("trace.log"))
+            }))
+        );
+        assert_eq!(
+            parse_args(args("--trace-file trace.log")),
+            Ok(Some(Options {
+                level: Level::Debug,
+                file: Some(PathBuf::from("trace.log"))
+            }))
+        );
+        assert!(parse_args(args("--trace=loud")).is_err());
+    }
+
+    #[test]
+    fn indents_lines_by_depth() {
+        let line = format_line(Duration::from_micros(1500), 2, format_args!("hello"));
+        assert_eq!(line, "[     1.500ms] │ │ hello\n");
+    }
+}
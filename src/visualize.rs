@@ -0,0 +1,251 @@
+/// Terminal rendering of grids with colored overlays, for watching a solution work.
+///
+/// Visualizations are compiled in with the `visualize` feature and shown when the solution runs with
+/// `--visualize`, both of which `cargo solve <day> --visualize` takes care of. Guard visualization code
+/// with [`enabled`], so it is removed from regular builds and never runs during tests:
+///
+/// ```
+/// # use advent_of_code::{grid::{Coord, Grid}, visualize::{self, Color, Frame}};
+/// let grid: Grid<char> = "..#\n...".parse().unwrap();
+/// if visualize::enabled() {
+///     visualize::show(Frame::new(&grid).highlight([Coord::new(0, 1)], Color::Yellow));
+/// }
+/// ```
+use std::fmt::Display;
+use std::io::{stdout, Write};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+use crate::direction::Direction;
+use crate::grid::{Coord, Grid};
+use crate::template::{ansi, ANSI_BOLD, ANSI_RESET};
+
+/// Whether visualizations are compiled in and were requested with `--visualize`.
+pub fn enabled() -> bool {
+    static REQUESTED: OnceLock<bool> = OnceLock::new();
+    cfg!(feature = "visualize")
+        && *REQUESTED.get_or_init(|| std::env::args().any(|arg| arg == "--visualize"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    /// The ANSI escape code that sets this foreground color.
+    pub fn code(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A snapshot of a grid with overlays. Overlays added later are drawn on top of earlier ones.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    glyphs: Grid<char>,
+    colors: Grid<Option<Color>>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// Draws every cell as the first character of its `Display` output.
+    pub fn new<T: Display>(grid: &Grid<T>) -> Self {
+        Self::with_glyphs(grid, |cell| cell.to_string().chars().next().unwrap_or(' '))
+    }
+
+    /// Draws every cell as the character returned by `glyph`.
+    pub fn with_glyphs<T>(grid: &Grid<T>, glyph: impl FnMut(&T) -> char) -> Self {
+        Self {
+            glyphs: grid.map(glyph),
+            colors: Grid::new(grid.width(), grid.height(), None),
+            caption: None,
+        }
+    }
+
+    /// Colors the given cells, keeping their glyphs. Cells outside of the grid are ignored.
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = Coord>, color: Color) -> Self {
+        for cell in cells {
+            if let Some(c) = self.colors.get_mut(cell) {
+                *c = Some(color);
+            }
+        }
+        self
+    }
+
+    /// Draws `glyph` in `color` on the given cells.
+    pub fn mark(
+        mut self,
+        cells: impl IntoIterator<Item = Coord>,
+        glyph: char,
+        color: Color,
+    ) -> Self {
+        for cell in cells {
+            if let Some(g) = self.glyphs.get_mut(cell) {
+                *g = glyph;
+                self.colors[cell] = Some(color);
+            }
+        }
+        self
+    }
+
+    /// Draws a path of neighbouring cells with arrows pointing to the next cell.
+    pub fn path(mut self, path: impl IntoIterator<Item = Coord>, color: Color) -> Self {
+        let path: Vec<Coord> = path.into_iter().collect();
+        for (i, &cell) in path.iter().enumerate() {
+            let arrow = path.get(i + 1).and_then(|&next| {
+                let delta = (
+                    next.x as isize - cell.x as isize,
+                    next.y as isize - cell.y as isize,
+                );
+                Direction::from_delta(delta)?.arrow()
+            });
+            self = self.mark([cell], arrow.unwrap_or('*'), color);
+        }
+        self
+    }
+
+    /// Text shown below the grid.
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// The frame as text, with ANSI colors unless the output format is plain.
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(self.glyphs.len() * 2);
+        for (coord, glyph) in self.glyphs.iter() {
+            if coord.x == 0 && coord.y > 0 {
+                out.push('\n');
+            }
+            match self.colors[coord] {
+                Some(color) => {
+                    out.push_str(ansi(color.code()));
+                    out.push(*glyph);
+                    out.push_str(ansi(ANSI_RESET));
+                }
+                None => out.push(*glyph),
+            }
+        }
+        if let Some(caption) = &self.caption {
+            out.push_str(&format!(
+                "\n{}{caption}{}",
+                ansi(ANSI_BOLD),
+                ansi(ANSI_RESET)
+            ));
+        }
+        out
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Prints a frame if visualizations are [`enabled`].
+pub fn show(frame: Frame) {
+    if enabled() {
+        println!("{}", frame.render());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Redraws frames in place, waiting `delay` after each one. Does nothing unless visualizations are [`enabled`].
+#[derive(Debug, Clone)]
+pub struct Animation {
+    delay: Duration,
+    frames: usize,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(50))
+    }
+}
+
+impl Animation {
+    pub fn new(delay: Duration) -> Self {
+        Self { delay, frames: 0 }
+    }
+
+    pub fn frame(&mut self, frame: Frame) {
+        if !enabled() {
+            return;
+        }
+        self.frames += 1;
+        // move to the top left and clear the screen before drawing.
+        let mut stdout = stdout().lock();
+        let _ = write!(
+            stdout,
+            "\x1b[H\x1b[2J{}\nframe {}\n",
+            frame.render(),
+            self.frames
+        );
+        let _ = stdout.flush();
+        thread::sleep(self.delay);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Color, Frame};
+    #[allow(unused_imports)]
+    use crate::grid::{Coord, Grid};
+
+    #[test]
+    fn renders_overlays() {
+        let grid: Grid<char> = "..#\n...".parse().unwrap();
+        let frame = Frame::new(&grid)
+            .path(
+                [Coord::new(0, 0), Coord::new(0, 1), Coord::new(1, 1)],
+                Color::Cyan,
+            )
+            .mark([Coord::new(2, 1), Coord::new(9, 9)], 'X', Color::Red)
+            .highlight([Coord::new(2, 0)], Color::Gray)
+            .caption("step 2");
+
+        let plain = frame
+            .render()
+            .split('\x1b')
+            .enumerate()
+            .map(|(i, part)| {
+                if i == 0 {
+                    part
+                } else {
+                    &part[part.find('m').unwrap() + 1..]
+                }
+            })
+            .collect::<String>();
+        assert_eq!(plain, "v.#\n>*X\nstep 2");
+    }
+
+    #[test]
+    fn draws_cells_with_custom_glyphs() {
+        let grid = Grid::from_vec(2, vec![1, 0, 0, 1]).unwrap();
+        let frame = Frame::with_glyphs(&grid, |&c| if c == 1 { '#' } else { ' ' });
+        assert_eq!(frame.render(), "# \n #");
+        assert_eq!(Frame::new(&grid).to_string(), "10\n01");
+    }
+}
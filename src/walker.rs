@@ -0,0 +1,202 @@
+/// Simulation of a walker that moves straight ahead over a [`Grid`] and turns when blocked,
+/// until it leaves the grid or enters a loop.
+use crate::bitgrid::{BitGrid, StateSet};
+use crate::direction::Direction;
+use crate::grid::{Coord, Grid};
+
+/// How a walk ended. A step is either a move to the next cell or a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The walker left the grid after `steps` steps.
+    Exit { steps: usize },
+    /// The walker repeats a state. The loop is entered after `start` steps and takes `length` steps.
+    Loop { start: usize, length: usize },
+}
+
+impl Outcome {
+    pub fn is_loop(&self) -> bool {
+        matches!(self, Outcome::Loop { .. })
+    }
+}
+
+/// Walks a grid from a start cell and heading.
+/// When the cell ahead is blocked, the walker turns with the turn rule (right by default), otherwise it moves.
+///
+/// Visited states are tracked in a [`StateSet`], which is reused across walks,
+/// so simulating many variants of a grid (e.g. with a temporary obstacle) does not allocate.
+///
+/// ```
+/// # use advent_of_code::{direction::Direction, grid::{Coord, Grid}, walker::{Outcome, Walker}};
+/// let grid: Grid<char> = ".#.\n..#\n...".parse().unwrap();
+/// let mut walker = Walker::new(&grid, |c| *c == '#');
+/// assert_eq!(walker.walk(Coord::new(1, 2), Direction::N), Outcome::Exit { steps: 4 });
+/// assert_eq!(walker.visited_cells(), 2);
+/// ```
+pub struct Walker<'a, T, F> {
+    grid: &'a Grid<T>,
+    blocked: F,
+    turn: fn(Direction) -> Direction,
+    obstacle: Option<Coord>,
+    states: StateSet,
+    cells: BitGrid,
+}
+
+impl<'a, T, F> Walker<'a, T, F>
+where
+    F: Fn(&T) -> bool,
+{
+    pub fn new(grid: &'a Grid<T>, blocked: F) -> Self {
+        Self {
+            grid,
+            blocked,
+            turn: Direction::turn_right,
+            obstacle: None,
+            states: StateSet::for_grid(grid),
+            cells: BitGrid::for_grid(grid),
+        }
+    }
+
+    /// Replaces the rule for turning at a blocked cell.
+    pub fn with_turn(mut self, turn: fn(Direction) -> Direction) -> Self {
+        self.turn = turn;
+        self
+    }
+
+    /// Treats `obstacle` as blocked in subsequent walks, in addition to the cells of the grid.
+    /// The grid itself is left untouched. Pass `None` to remove it.
+    pub fn set_obstacle(&mut self, obstacle: Option<Coord>) {
+        self.obstacle = obstacle;
+    }
+
+    pub fn is_blocked(&self, coord: Coord) -> bool {
+        self.obstacle == Some(coord) || (self.blocked)(&self.grid[coord])
+    }
+
+    /// The state following `(position, heading)`, or `None` if the walker leaves the grid.
+    pub fn step(&self, position: Coord, heading: Direction) -> Option<(Coord, Direction)> {
+        let ahead = self.grid.offset(position, heading)?;
+        if self.is_blocked(ahead) {
+            Some((position, (self.turn)(heading)))
+        } else {
+            Some((ahead, heading))
+        }
+    }
+
+    /// Walks from `start` until the walker leaves the grid or repeats a state.
+    pub fn walk(&mut self, start: Coord, heading: Direction) -> Outcome {
+        self.states.clear();
+        self.cells.clear();
+
+        let mut state = (start, heading);
+        let mut steps = 0;
+
+        loop {
+            self.cells.insert(state.0);
+            if !self.states.insert(state.0, state.1) {
+                // the first repeated state is where the loop is entered.
+                let length = self.cycle_length(state);
+                return Outcome::Loop {
+                    start: steps - length,
+                    length,
+                };
+            }
+
+            match self.step(state.0, state.1) {
+                Some(next) => state = next,
+                None => return Outcome::Exit { steps },
+            }
+            steps += 1;
+        }
+    }
+
+    fn cycle_length(&self, start: (Coord, Direction)) -> usize {
+        let mut state = start;
+        let mut length = 0;
+        loop {
+            state = self
+                .step(state.0, state.1)
+                .expect("a loop never leaves the grid");
+            length += 1;
+            if state == start {
+                return length;
+            }
+        }
+    }
+
+    /// Number of distinct cells visited in the last walk.
+    pub fn visited_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Cells visited in the last walk, in row-major order.
+    pub fn visited(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.cells.iter()
+    }
+
+    /// Whether the last walk visited `coord` with `heading`.
+    pub fn has_visited(&self, coord: Coord, heading: Direction) -> bool {
+        self.states.contains(coord, heading)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[allow(unused_imports)]
+    use super::{Outcome, Walker};
+    #[allow(unused_imports)]
+    use crate::{
+        direction::Direction,
+        grid::{Coord, Grid},
+    };
+
+    #[allow(dead_code)]
+    fn grid() -> Grid<char> {
+        [".#..", "...#", "#...", "..#."].join("\n").parse().unwrap()
+    }
+
+    #[test]
+    fn exits_the_grid() {
+        let grid = grid();
+        let mut walker = Walker::new(&grid, |c| *c == '#');
+        let outcome = walker.walk(Coord::new(3, 3), Direction::N);
+        assert_eq!(outcome, Outcome::Exit { steps: 2 });
+        assert_eq!(
+            walker.visited().collect::<Vec<_>>(),
+            vec![Coord::new(3, 2), Coord::new(3, 3)]
+        );
+        assert!(walker.has_visited(Coord::new(3, 2), Direction::E));
+        assert!(!walker.has_visited(Coord::new(3, 3), Direction::E));
+    }
+
+    #[test]
+    fn detects_loops() {
+        let grid = grid();
+        let mut walker = Walker::new(&grid, |c| *c == '#');
+        // one move north, then circling clockwise around the center.
+        let outcome = walker.walk(Coord::new(1, 3), Direction::N);
+        assert_eq!(
+            outcome,
+            Outcome::Loop {
+                start: 1,
+                length: 8
+            }
+        );
+        assert_eq!(walker.visited_cells(), 5);
+    }
+
+    #[test]
+    fn injects_obstacles_without_touching_the_grid() {
+        let grid: Grid<char> = "...\n...\n...".parse().unwrap();
+        let mut walker = Walker::new(&grid, |c| *c == '#');
+        assert!(!walker.walk(Coord::new(1, 2), Direction::N).is_loop());
+
+        walker.set_obstacle(Some(Coord::new(1, 0)));
+        assert_eq!(
+            walker.walk(Coord::new(1, 2), Direction::N),
+            Outcome::Exit { steps: 3 }
+        );
+        assert!(grid.find(|c| *c == '#').is_none());
+    }
+}